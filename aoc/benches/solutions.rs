@@ -0,0 +1,195 @@
+//! Benchmarks every day's parse step and both parts on the checked-in
+//! inputs, plus synthetic inputs of increasing size for the days whose cost
+//! grows with the input. To compare against an earlier run:
+//!
+//! ```text
+//! cargo bench -p aoc -- --save-baseline before
+//! cargo bench -p aoc -- --baseline before
+//! ```
+
+use aoc::registry;
+use common::Solution;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::fs;
+
+fn read_input(day: u8) -> String {
+    let day = registry::find(day).expect("registered day");
+    day.input_path()
+        .map(|path| fs::read_to_string(path).expect("Failed to read input"))
+        .unwrap_or_default()
+}
+
+fn bench_day<S: Solution>(c: &mut Criterion, day: u8) {
+    let input = read_input(day);
+    let parsed = S::parse(&input).expect("Failed to parse input");
+
+    let mut group = c.benchmark_group(format!("day{}", day));
+    group.sample_size(10);
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+    group.finish();
+}
+
+fn inputs(c: &mut Criterion) {
+    bench_day::<day1::Day1>(c, 1);
+    bench_day::<day2::Day2>(c, 2);
+    bench_day::<day3::Day3>(c, 3);
+    bench_day::<day4::Day4>(c, 4);
+    bench_day::<day5::Day5>(c, 5);
+    bench_day::<day6::Day6>(c, 6);
+    bench_day::<day7::Day7>(c, 7);
+    bench_day::<day8::Day8>(c, 8);
+    bench_day::<day9::Day9>(c, 9);
+    bench_day::<day10::Day10>(c, 10);
+    bench_day::<day11::Day11>(c, 11);
+    bench_day::<day12::Day12>(c, 12);
+    bench_day::<day13::Day13>(c, 13);
+}
+
+/// A small xorshift generator so the synthetic inputs are the same on
+/// every run.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % bound
+    }
+
+    fn between(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low + 1) as u64) as i64
+    }
+}
+
+fn masses(count: usize) -> String {
+    let mut rng = Rng(0x2019_0001);
+    (0..count)
+        .map(|_| format!("{}\n", rng.between(1_000, 200_000)))
+        .collect()
+}
+
+/// Two random wires whose opening segments always cross, so there is at
+/// least one intersection to find.
+fn wires(segments: usize) -> String {
+    let mut rng = Rng(0x2019_0003);
+    let mut wire = |start: &str| {
+        let rest = (0..segments).map(|i| {
+            let direction = if i % 2 == 0 { ["R", "L"] } else { ["U", "D"] };
+            let direction = direction[rng.below(2) as usize];
+            format!("{}{}", direction, rng.between(1, 1_000))
+        });
+        start
+            .split(',')
+            .map(String::from)
+            .chain(rest)
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+    format!("{}\n{}\n", wire("R500,U500"), wire("U5,R250,D10,L250"))
+}
+
+fn orbits(bodies: usize) -> String {
+    let mut rng = Rng(0x2019_0006);
+    let name = |i: u64| {
+        if i == 0 {
+            "COM".to_string()
+        } else {
+            format!("B{}", i)
+        }
+    };
+    let mut lines = (1..bodies as u64)
+        .map(|i| format!("{}){}", name(rng.below(i)), name(i)))
+        .collect::<Vec<String>>();
+    lines.push(format!("{})YOU", name(rng.below(bodies as u64))));
+    lines.push(format!("{})SAN", name(rng.below(bodies as u64))));
+    lines.join("\n")
+}
+
+fn asteroids(size: usize) -> String {
+    let mut rng = Rng(0x2019_0010);
+    (0..size)
+        .map(|_| {
+            let row = (0..size)
+                .map(|_| if rng.below(10) < 3 { '#' } else { '.' })
+                .collect::<String>();
+            format!("{}\n", row)
+        })
+        .collect()
+}
+
+fn moons(count: usize) -> String {
+    let mut rng = Rng(0x2019_0012);
+    (0..count)
+        .map(|_| {
+            format!(
+                "<x={}, y={}, z={}>\n",
+                rng.between(-10, 10),
+                rng.between(-10, 10),
+                rng.between(-10, 10)
+            )
+        })
+        .collect()
+}
+
+fn scaled(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scaled/day1");
+    for &count in &[100, 1_000, 10_000] {
+        let input = day1::parse(&masses(count)).expect("synthetic input");
+        group.bench_with_input(BenchmarkId::new("part2", count), &input, |b, input| {
+            b.iter(|| day1::part2(input))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("scaled/day3");
+    group.sample_size(10);
+    for &segments in &[50, 150, 300] {
+        let input = wires(segments);
+        group.bench_with_input(BenchmarkId::new("parse", segments), &input, |b, input| {
+            b.iter(|| day3::parse(input))
+        });
+        let input = day3::parse(&input).expect("synthetic input");
+        group.bench_with_input(BenchmarkId::new("part2", segments), &input, |b, input| {
+            b.iter(|| day3::part2(input))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("scaled/day6");
+    group.sample_size(10);
+    for &bodies in &[1_000, 10_000, 50_000] {
+        let input = day6::parse(&orbits(bodies)).expect("synthetic input");
+        group.bench_with_input(BenchmarkId::new("part1", bodies), &input, |b, input| {
+            b.iter(|| day6::part1(input))
+        });
+        group.bench_with_input(BenchmarkId::new("part2", bodies), &input, |b, input| {
+            b.iter(|| day6::part2(input))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("scaled/day10");
+    group.sample_size(10);
+    for &size in &[10, 20, 30] {
+        let input = day10::parse(&asteroids(size)).expect("synthetic input");
+        group.bench_with_input(BenchmarkId::new("part1", size), &input, |b, input| {
+            b.iter(|| day10::part1(input))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("scaled/day12");
+    for &count in &[4, 8, 16] {
+        let input = day12::parse(&moons(count)).expect("synthetic input");
+        group.bench_with_input(BenchmarkId::new("part1", count), &input, |b, input| {
+            b.iter(|| day12::part1(input))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, inputs, scaled);
+criterion_main!(benches);
@@ -0,0 +1,131 @@
+use aoc::registry::{self, Day, DayReport, DAYS};
+use clap::{Parser, Subcommand};
+use common::{ocr, Answer};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+#[derive(Parser)]
+#[command(about = "Runs the advent of code 2019 solutions")]
+struct Options {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day, or `all` of them
+    Run {
+        day: String,
+        /// Only run this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Read the input from this file instead of dayN/input.txt
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Print the answers as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+fn run(day: &Day, part: Option<u8>, input: Option<&PathBuf>) -> Result<DayReport, String> {
+    let path = input.cloned().or_else(|| day.input_path());
+    let describe = |e: &dyn fmt::Display| match &path {
+        Some(path) => format!("{}: {}", path.display(), e),
+        None => e.to_string(),
+    };
+
+    let input = match &path {
+        Some(path) => fs::read_to_string(path).map_err(|e| describe(&e))?,
+        None => String::new(),
+    };
+    let (parse_elapsed, parts) = (day.solve)(&input, part).map_err(|e| describe(&e))?;
+    Ok(DayReport {
+        day: day.number,
+        parse_elapsed,
+        parts,
+    })
+}
+
+fn print_report(report: &DayReport) {
+    println!(
+        "day {} (parsed in {:.3?})",
+        report.day, report.parse_elapsed
+    );
+    for part in &report.parts {
+        match &part.answer {
+            Answer::Number(value) => {
+                println!("  part {} = {} ({:.3?})", part.part, value, part.elapsed);
+            }
+            Answer::Error(message) => {
+                println!(
+                    "  part {}: no answer, {} ({:.3?})",
+                    part.part, message, part.elapsed
+                );
+            }
+            Answer::Grid(grid) => match ocr::recognize(grid) {
+                Ok(letters) => {
+                    println!("  part {} = {} ({:.3?})", part.part, letters, part.elapsed);
+                }
+                Err(_) => {
+                    println!("  part {} ({:.3?}):", part.part, part.elapsed);
+                    for row in grid.rows() {
+                        println!("    {}", row);
+                    }
+                }
+            },
+        }
+    }
+}
+
+fn main() {
+    let Options { command } = Options::parse();
+    let Command::Run {
+        day,
+        part,
+        input,
+        json,
+    } = command;
+
+    let days = if day == "all" {
+        if input.is_some() {
+            eprintln!("--input cannot be used with `all`");
+            process::exit(2);
+        }
+
+        DAYS.iter().collect::<Vec<&Day>>()
+    } else {
+        let day = day
+            .parse()
+            .ok()
+            .and_then(registry::find)
+            .unwrap_or_else(|| {
+                eprintln!("unknown day {}", day);
+                process::exit(2);
+            });
+        vec![day]
+    };
+
+    let mut reports = vec![];
+    for day in days {
+        match run(day, part, input.as_ref()) {
+            Ok(report) => {
+                if !json {
+                    print_report(&report);
+                }
+                reports.push(report);
+            }
+            Err(e) => {
+                eprintln!("day {}: {}", day.number, e);
+                process::exit(1);
+            }
+        }
+    }
+
+    if json {
+        let output = serde_json::to_string_pretty(&reports).expect("Failed to serialize");
+        println!("{}", output);
+    }
+}
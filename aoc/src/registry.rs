@@ -0,0 +1,130 @@
+use common::{ocr, Answer, ParseError, Solution};
+use serde::ser::SerializeMap;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Serialize)]
+pub struct PartReport {
+    pub part: u8,
+    #[serde(serialize_with = "as_json")]
+    pub answer: Answer,
+    #[serde(rename = "time_ms", serialize_with = "as_millis")]
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayReport {
+    pub day: u8,
+    #[serde(rename = "parse_time_ms", serialize_with = "as_millis")]
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+fn as_millis<S: serde::Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+/// Numbers are written as JSON numbers and grids as the letters they spell,
+/// or as an array of rows when they can't be read. A part without an answer
+/// is written as `{"error": message}`.
+fn as_json<S: serde::Serializer>(answer: &Answer, serializer: S) -> Result<S::Ok, S::Error> {
+    match answer {
+        Answer::Number(value) => serializer.serialize_i128(*value),
+        Answer::Error(message) => {
+            let mut map = serializer.serialize_map(Some(1))?;
+            map.serialize_entry("error", message)?;
+            map.end()
+        }
+        Answer::Grid(grid) => match ocr::recognize(grid) {
+            Ok(letters) => serializer.serialize_str(&letters),
+            Err(_) => serializer.collect_seq(grid.rows()),
+        },
+    }
+}
+
+/// How long parsing took, along with the answers for the parts that ran.
+pub type Solved = (Duration, Vec<PartReport>);
+
+pub struct Day {
+    pub number: u8,
+    pub has_input: bool,
+    pub solve: fn(&str, Option<u8>) -> Result<Solved, ParseError>,
+}
+
+impl Day {
+    /// Where the checked-in puzzle input lives, under the workspace the
+    /// runner was built in, so it is found from any directory.
+    pub fn input_path(&self) -> Option<PathBuf> {
+        if self.has_input {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .parent()
+                .expect("workspace root");
+            Some(root.join(format!("day{}", self.number)).join("input.txt"))
+        } else {
+            None
+        }
+    }
+}
+
+fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn solve<S: Solution>(input: &str, part: Option<u8>) -> Result<Solved, ParseError> {
+    let (input, parse_elapsed) = timed(|| S::parse(input));
+    let input = input?;
+    let parts: [fn(&S::Input) -> Answer; 2] = [
+        |input| S::part1(input).into(),
+        |input| S::part2(input).into(),
+    ];
+    let reports = (1..=2)
+        .zip(parts.iter())
+        .filter(|&(number, _)| part.is_none_or(|part| part == number))
+        .map(|(number, f)| {
+            let (answer, elapsed) = timed(|| f(&input));
+            PartReport {
+                part: number,
+                answer,
+                elapsed,
+            }
+        })
+        .collect();
+
+    Ok((parse_elapsed, reports))
+}
+
+macro_rules! day {
+    ($number:expr, $solution:ty) => {
+        day!($number, $solution, true)
+    };
+    ($number:expr, $solution:ty, $has_input:expr) => {
+        Day {
+            number: $number,
+            has_input: $has_input,
+            solve: solve::<$solution>,
+        }
+    };
+}
+
+pub const DAYS: [Day; 13] = [
+    day!(1, day1::Day1),
+    day!(2, day2::Day2),
+    day!(3, day3::Day3),
+    day!(4, day4::Day4),
+    day!(5, day5::Day5),
+    day!(6, day6::Day6),
+    day!(7, day7::Day7),
+    day!(8, day8::Day8),
+    day!(9, day9::Day9),
+    day!(10, day10::Day10),
+    day!(11, day11::Day11),
+    day!(12, day12::Day12),
+    day!(13, day13::Day13),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,113 @@
+//! Checks every day's answers for its checked-in input against
+//! `answers.toml` at the root of the workspace. After an intentional change,
+//! record the new answers with:
+//!
+//! ```text
+//! cargo test -p aoc --test answers -- --bless
+//! ```
+
+use aoc::registry::DAYS;
+use common::{ocr, Answer};
+use std::convert::TryFrom;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use toml::{Table, Value};
+
+fn root() -> PathBuf {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest.parent().expect("workspace root").to_path_buf()
+}
+
+fn to_toml(answer: &Answer) -> Value {
+    match answer {
+        Answer::Number(value) => match i64::try_from(*value) {
+            Ok(value) => Value::Integer(value),
+            Err(_) => Value::String(value.to_string()),
+        },
+        Answer::Error(message) => Value::String(format!("no answer, {}", message)),
+        Answer::Grid(grid) => match ocr::recognize(grid) {
+            Ok(letters) => Value::String(letters),
+            Err(_) => Value::Array(grid.rows().into_iter().map(Value::String).collect()),
+        },
+    }
+}
+
+/// Grids are written one row per line so they read as the picture.
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Array(rows) => {
+            let rows = rows
+                .iter()
+                .map(|row| format!("  {},\n", row))
+                .collect::<String>();
+            format!("[\n{}]", rows)
+        }
+        _ => value.to_string(),
+    }
+}
+
+fn main() {
+    let bless = env::args().any(|arg| arg == "--bless");
+    let root = root();
+    let path = root.join("answers.toml");
+
+    let expected = if bless {
+        Table::new()
+    } else {
+        fs::read_to_string(&path)
+            .expect("Failed to read answers.toml")
+            .parse::<Table>()
+            .expect("Failed to parse answers.toml")
+    };
+
+    let mut blessed = String::new();
+    let mut failures = 0;
+    for day in DAYS.iter() {
+        let input = day
+            .input_path()
+            .map(|input| fs::read_to_string(input).expect("Failed to read input"))
+            .unwrap_or_default();
+        let (_, parts) = (day.solve)(&input, None).expect("Failed to parse input");
+
+        let key = format!("day{}", day.number);
+        if !blessed.is_empty() {
+            blessed.push('\n');
+        }
+        writeln!(blessed, "[{}]", key).unwrap();
+
+        for part in parts {
+            let name = format!("part{}", part.part);
+            let actual = to_toml(&part.answer);
+            writeln!(blessed, "{} = {}", name, format_value(&actual)).unwrap();
+            if bless {
+                continue;
+            }
+
+            let label = format!("day {} part {}", day.number, part.part);
+            match expected.get(&key).and_then(|day| day.get(&name)) {
+                Some(expected) if *expected == actual => println!("{} ... ok", label),
+                Some(expected) => {
+                    failures += 1;
+                    println!("{} ... FAILED", label);
+                    println!("  expected: {}", format_value(expected));
+                    println!("    actual: {}", format_value(&actual));
+                }
+                None => {
+                    failures += 1;
+                    println!("{} ... MISSING (run with --bless to record it)", label);
+                }
+            }
+        }
+    }
+
+    if bless {
+        fs::write(&path, blessed).expect("Failed to write answers.toml");
+        println!("recorded answers in {}", path.display());
+    } else if failures > 0 {
+        println!("{} answer(s) did not match answers.toml", failures);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::Grid;
+use std::convert::TryFrom;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    /// Wide enough for any of the integer types the days answer with.
+    Number(i128),
+    Grid(Grid),
+    /// Why a part has no answer for the input it was given.
+    Error(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(value) => write!(f, "{}", value),
+            Answer::Grid(grid) => write!(f, "{}", grid),
+            Answer::Error(message) => write!(f, "no answer: {}", message),
+        }
+    }
+}
+
+impl From<Grid> for Answer {
+    fn from(source: Grid) -> Answer {
+        Answer::Grid(source)
+    }
+}
+
+impl<T: Into<Answer>, E: fmt::Display> From<Result<T, E>> for Answer {
+    fn from(source: Result<T, E>) -> Answer {
+        match source {
+            Ok(answer) => answer.into(),
+            Err(e) => Answer::Error(e.to_string()),
+        }
+    }
+}
+
+macro_rules! number_answer {
+    ($($source:ty),*) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(source: $source) -> Answer {
+                    // Lossless, as none of them is wider than 64 bits.
+                    Answer::Number(source as i128)
+                }
+            }
+        )*
+    };
+}
+
+number_answer!(u16, i32, u32, i64, u64, isize, usize);
+
+impl From<u128> for Answer {
+    fn from(source: u128) -> Answer {
+        match i128::try_from(source) {
+            Ok(value) => Answer::Number(value),
+            Err(_) => Answer::Error(format!("{} is too large to answer with", source)),
+        }
+    }
+}
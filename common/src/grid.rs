@@ -0,0 +1,5 @@
+use geometry::DenseGrid;
+
+/// A rendered picture of lit and unlit pixels, such as the letters painted
+/// by day 8 and day 11.
+pub type Grid = DenseGrid<bool>;
@@ -0,0 +1,62 @@
+mod answer;
+mod grid;
+pub mod ocr;
+pub mod parse;
+
+pub use answer::Answer;
+pub use grid::Grid;
+pub use parse::ParseError;
+
+use std::io::{self, Read};
+use std::process;
+
+/// A day's puzzle, split into parsing the input once and answering each
+/// part from the parsed form.
+pub trait Solution {
+    type Input;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+pub fn print_answer(part: u8, answer: &Answer) {
+    match answer {
+        Answer::Number(value) => println!("part {} = {}", part, value),
+        Answer::Error(message) => println!("part {}: no answer, {}", part, message),
+        Answer::Grid(grid) => match ocr::recognize(grid) {
+            Ok(letters) => println!("part {} = {}", part, letters),
+            Err(_) => {
+                println!("part {}:", part);
+                println!("{}", grid);
+            }
+        },
+    }
+}
+
+/// Reads and parses the puzzle input from stdin, exiting with the error if it
+/// can't be parsed.
+pub fn read_input<S: Solution>() -> S::Input {
+    let mut input = String::new();
+    io::stdin()
+        .lock()
+        .read_to_string(&mut input)
+        .expect("Failed to read data");
+
+    S::parse(&input).unwrap_or_else(|e| {
+        eprintln!("Failed to parse input: {}", e);
+        process::exit(1);
+    })
+}
+
+pub fn print_answers<S: Solution>(input: &S::Input) {
+    print_answer(1, &S::part1(input).into());
+    print_answer(2, &S::part2(input).into());
+}
+
+/// Runs both parts of a solution against stdin, as each day's binary does.
+pub fn run<S: Solution>() {
+    print_answers::<S>(&read_input::<S>());
+}
@@ -0,0 +1,111 @@
+//! Reads the block letters that day 8 and day 11 draw. Every letter is six
+//! pixels tall and letters are separated by at least one unlit column.
+
+use crate::Grid;
+use geometry::Point;
+use std::error::Error;
+use std::fmt;
+
+const HEIGHT: usize = 6;
+
+const FONT: &[(char, [&str; HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OcrError {
+    /// The picture isn't one row of letters.
+    Height(usize),
+    /// The pixels starting at `column` don't match any letter in the font.
+    UnknownGlyph { column: usize, rows: Vec<String> },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::Height(height) => write!(
+                f,
+                "expected letters {} pixels tall, found {}",
+                HEIGHT, height
+            ),
+            OcrError::UnknownGlyph { column, rows } => {
+                write!(f, "unknown glyph at column {}:", column)?;
+                for row in rows {
+                    write!(f, "\n{}", row)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+fn is_blank(grid: &Grid, x: usize) -> bool {
+    (0..grid.height()).all(|y| grid.get(Point::new(x as i64, y as i64)) != Some(&true))
+}
+
+fn glyph(grid: &Grid, columns: &[usize]) -> Vec<String> {
+    (0..grid.height())
+        .map(|y| {
+            columns
+                .iter()
+                .map(|&x| match grid.get(Point::new(x as i64, y as i64)) {
+                    Some(true) => '#',
+                    _ => '.',
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Recognizes the letters drawn in `grid`, ignoring any unlit border.
+pub fn recognize(grid: &Grid) -> Result<String, OcrError> {
+    if grid.height() != HEIGHT {
+        return Err(OcrError::Height(grid.height()));
+    }
+
+    let mut letters = String::new();
+    let mut columns = Vec::new();
+    for x in 0..=grid.width() {
+        if x < grid.width() && !is_blank(grid, x) {
+            columns.push(x);
+            continue;
+        }
+
+        if columns.is_empty() {
+            continue;
+        }
+
+        let rows = glyph(grid, &columns);
+        let letter = FONT
+            .iter()
+            .find(|(_, pattern)| pattern.iter().eq(rows.iter()))
+            .map(|&(letter, _)| letter)
+            .ok_or_else(|| OcrError::UnknownGlyph {
+                column: columns[0],
+                rows: rows.clone(),
+            })?;
+        letters.push(letter);
+        columns.clear();
+    }
+
+    Ok(letters)
+}
@@ -0,0 +1,104 @@
+//! Helpers for parsing puzzle inputs that report where in the input a
+//! problem was found. Each helper takes the whole `input` along with the
+//! piece of it being parsed, which must be a slice of `input`, so the
+//! piece's line and column can be recovered when it turns out to be bad.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    /// 1-based line of the offending text
+    pub line: usize,
+    /// 1-based column, counted in characters, of the offending text
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Builds an error about `text`, which should be a slice of `input`.
+    /// Other text is looked for in `input`, and placed at its end if it
+    /// isn't there.
+    pub fn new<M: Into<String>>(input: &str, text: &str, message: M) -> ParseError {
+        let offset = (text.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| {
+                offset
+                    .checked_add(text.len())
+                    .is_some_and(|end| end <= input.len())
+                    && input.is_char_boundary(offset)
+            })
+            .or_else(|| input.find(text))
+            .unwrap_or(input.len());
+
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count() + 1;
+
+        ParseError {
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+const DISPLAY_LIMIT: usize = 40;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}, found `",
+            self.line, self.column, self.message
+        )?;
+        if self.text.chars().count() > DISPLAY_LIMIT {
+            let text = self.text.chars().take(DISPLAY_LIMIT).collect::<String>();
+            write!(f, "{}...`", text)
+        } else {
+            write!(f, "{}`", self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// The non-blank lines of `input`, with surrounding whitespace removed.
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().map(str::trim).filter(|line| !line.is_empty())
+}
+
+pub fn number<T: FromStr>(input: &str, text: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::new(input, text, "expected a number"))
+}
+
+/// Splits `text` around the first `separator`, both sides of which must be
+/// present.
+pub fn split_once<'a>(
+    input: &str,
+    text: &'a str,
+    separator: char,
+) -> Result<(&'a str, &'a str), ParseError> {
+    match text.find(separator) {
+        Some(index) => Ok((&text[..index], &text[index + separator.len_utf8()..])),
+        None => Err(ParseError::new(
+            input,
+            text,
+            format!("expected `{}`", separator),
+        )),
+    }
+}
+
+/// Parses the comma-separated numbers used for Intcode programs.
+pub fn comma_separated<T: FromStr>(input: &str) -> Result<Vec<T>, ParseError> {
+    input
+        .trim()
+        .split(',')
+        .map(|item| number(input, item.trim()))
+        .collect()
+}
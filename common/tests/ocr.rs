@@ -0,0 +1,67 @@
+use common::ocr::{recognize, OcrError};
+use common::Grid;
+use geometry::Point;
+
+fn grid(rows: &[&str]) -> Grid {
+    let mut grid = Grid::new(rows[0].len(), rows.len());
+    for (y, row) in rows.iter().enumerate() {
+        for (x, pixel) in row.chars().enumerate() {
+            grid.set(Point::new(x as i64, y as i64), pixel == '#');
+        }
+    }
+    grid
+}
+
+#[test]
+fn reads_day_8_letters() {
+    let picture = grid(&[
+        "####..##..#..#.####.####.",
+        "#....#..#.#..#.#....#....",
+        "###..#..#.####.###..###..",
+        "#....####.#..#.#....#....",
+        "#....#..#.#..#.#....#....",
+        "#....#..#.#..#.####.#....",
+    ]);
+    assert_eq!(recognize(&picture), Ok("FAHEF".to_string()));
+}
+
+#[test]
+fn reads_day_11_letters_inside_a_border() {
+    let picture = grid(&[
+        "...##.#..#..##..###..###...##...##..#..#.",
+        "....#.#..#.#..#.#..#.#..#.#..#.#..#.#..#.",
+        "....#.####.#..#.#..#.###..#....#....#..#.",
+        "....#.#..#.####.###..#..#.#.##.#....#..#.",
+        ".#..#.#..#.#..#.#.#..#..#.#..#.#..#.#..#.",
+        "..##..#..#.#..#.#..#.###...###..##...##..",
+    ]);
+    assert_eq!(recognize(&picture), Ok("JHARBGCU".to_string()));
+}
+
+#[test]
+fn reports_unknown_glyphs() {
+    let picture = grid(&["####.#", "#..#.#", "#..#.#", "#..#.#", "#..#.#", "####.#"]);
+    let rows = ["####", "#..#", "#..#", "#..#", "#..#", "####"];
+    let error = recognize(&picture).unwrap_err();
+    assert_eq!(
+        error,
+        OcrError::UnknownGlyph {
+            column: 0,
+            rows: rows.iter().map(|row| row.to_string()).collect(),
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "unknown glyph at column 0:\n####\n#..#\n#..#\n#..#\n#..#\n####"
+    );
+}
+
+#[test]
+fn reports_pictures_of_the_wrong_height() {
+    let picture = grid(&["#", "#", "#"]);
+    assert_eq!(recognize(&picture), Err(OcrError::Height(3)));
+    assert_eq!(
+        OcrError::Height(3).to_string(),
+        "expected letters 6 pixels tall, found 3"
+    );
+}
@@ -0,0 +1,57 @@
+use common::parse::{comma_separated, number, split_once};
+use common::ParseError;
+
+#[test]
+fn finds_the_line_and_column_of_text() {
+    let input = "first line\nsecond ünïcode line\n\nlast";
+    let at = |text: &str| {
+        let error = ParseError::new(input, text, "bad");
+        (error.line, error.column)
+    };
+
+    assert_eq!(at(&input[..5]), (1, 1));
+    assert_eq!(at(&input[6..10]), (1, 7));
+    let line = input.find("line\n\n").unwrap();
+    assert_eq!(at(&input[line..line + 4]), (2, 16));
+    assert_eq!(at(&input[input.len() - 4..]), (4, 1));
+}
+
+#[test]
+fn places_text_at_the_end_of_the_input() {
+    let input = "1,\n2,";
+    let error = ParseError::new(input, &input[input.len()..], "expected a number");
+    assert_eq!((error.line, error.column), (2, 3));
+    assert_eq!(error.text, "");
+
+    let error = comma_separated::<i64>(input).unwrap_err();
+    assert_eq!((error.line, error.column), (2, 3));
+}
+
+#[test]
+fn looks_for_text_from_elsewhere() {
+    let input = "a\nb = 12";
+    let error = ParseError::new(input, "12", "bad");
+    assert_eq!((error.line, error.column), (2, 5));
+    let error = ParseError::new(input, "missing", "bad");
+    assert_eq!((error.line, error.column), (2, 7));
+}
+
+#[test]
+fn describes_errors_with_their_place() {
+    let input = "x)y\nab";
+    let error = split_once(input, &input[4..], ')').unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "line 2, column 1: expected `)`, found `ab`"
+    );
+
+    let long = "9".repeat(50);
+    let error = number::<u8>(&long, &long).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "line 1, column 1: expected a number, found `{}...`",
+            &long[..40]
+        )
+    );
+}
@@ -0,0 +1,148 @@
+//! The rocket equation: the fuel a mass needs is the mass divided by some
+//! divisor, rounded, less an offset, and never below zero. Fuel has mass
+//! too, so the total also covers the fuel for the fuel, and so on.
+
+use num_integer::Integer;
+use num_traits::CheckedAdd;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Anything fuel can be worked out for, such as `u64` or `BigUint`.
+pub trait Mass: Clone + Integer + CheckedAdd + From<u64> + FromStr + fmt::Display {}
+
+impl<T: Clone + Integer + CheckedAdd + From<u64> + FromStr + fmt::Display> Mass for T {}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Rounding {
+    Down,
+    Up,
+    /// To the nearest whole number, with halves rounding up.
+    Nearest,
+}
+
+impl FromStr for Rounding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Rounding, String> {
+        match s {
+            "down" => Ok(Rounding::Down),
+            "up" => Ok(Rounding::Up),
+            "nearest" => Ok(Rounding::Nearest),
+            _ => Err(format!("expected `down`, `up` or `nearest`, found `{}`", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FormulaError {
+    /// Dividing by less than two wouldn't make the fuel for fuel shrink.
+    Divisor(u64),
+    /// Rounding up leaves a mass of one needing fuel unless the offset
+    /// takes it away.
+    Offset(Rounding),
+}
+
+impl fmt::Display for FormulaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormulaError::Divisor(divisor) => {
+                write!(f, "divisor must be at least 2, found {}", divisor)
+            }
+            FormulaError::Offset(rounding) => {
+                write!(f, "rounding {:?} needs an offset of at least 1", rounding)
+            }
+        }
+    }
+}
+
+impl Error for FormulaError {}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Formula {
+    divisor: u64,
+    offset: u64,
+    rounding: Rounding,
+}
+
+impl Formula {
+    /// The formula from the puzzle, `mass / 3 - 2` rounding down.
+    pub const ROCKET: Formula = Formula {
+        divisor: 3,
+        offset: 2,
+        rounding: Rounding::Down,
+    };
+
+    /// Checks the formula always needs less fuel than the mass it is for,
+    /// so the fuel for fuel eventually runs out.
+    pub fn new(divisor: u64, offset: u64, rounding: Rounding) -> Result<Formula, FormulaError> {
+        if divisor < 2 {
+            return Err(FormulaError::Divisor(divisor));
+        }
+        if rounding != Rounding::Down && offset == 0 {
+            return Err(FormulaError::Offset(rounding));
+        }
+
+        Ok(Formula {
+            divisor,
+            offset,
+            rounding,
+        })
+    }
+
+    pub fn divisor(&self) -> u64 {
+        self.divisor
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    pub fn fuel<M: Mass>(&self, mass: &M) -> M {
+        let divisor = M::from(self.divisor);
+        let quotient = match self.rounding {
+            Rounding::Down => mass.div_floor(&divisor),
+            Rounding::Up => Integer::div_ceil(mass, &divisor),
+            Rounding::Nearest => {
+                // Adding half the divisor first could overflow the mass.
+                let (quotient, remainder) = mass.div_mod_floor(&divisor);
+                if remainder >= M::from(self.divisor - self.divisor / 2) {
+                    quotient + M::one()
+                } else {
+                    quotient
+                }
+            }
+        };
+
+        let offset = M::from(self.offset);
+        if quotient > offset {
+            quotient - offset
+        } else {
+            M::zero()
+        }
+    }
+
+    /// The fuel for `mass` plus the fuel for that fuel, and so on. Each
+    /// step divides the mass, so this takes a logarithmic number of steps,
+    /// and the total never exceeds the mass.
+    pub fn total_fuel<M: Mass>(&self, mass: &M) -> M {
+        let mut total = M::zero();
+        let mut fuel = self.fuel(mass);
+        while !fuel.is_zero() {
+            let next = self.fuel(&fuel);
+            total = total + fuel;
+            fuel = next;
+        }
+        total
+    }
+}
+
+impl Default for Formula {
+    fn default() -> Formula {
+        Formula::ROCKET
+    }
+}
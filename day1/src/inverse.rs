@@ -0,0 +1,69 @@
+//! Works the rocket equation backwards, from a fuel budget to the masses it
+//! can lift. More mass never needs less fuel, for a module's own fuel or
+//! the total including the fuel for fuel, so both can be inverted by
+//! searching.
+
+use crate::fuel::{Formula, Mass};
+use std::ops::RangeInclusive;
+
+/// The largest mass for which `fuel` is at most `budget`, or the largest
+/// mass `M` can hold if the budget covers that. `fuel` must never decrease
+/// as the mass grows.
+fn largest_within<M: Mass>(budget: &M, fuel: impl Fn(&M) -> M) -> M {
+    // Take ever larger steps while the fuel stays within budget, and
+    // smaller ones once it doesn't, keeping `fuel(low) <= budget`. No mass
+    // needs negative fuel, so that starts out true.
+    let mut low = M::zero();
+    let mut step = M::one();
+    loop {
+        match low.checked_add(&step) {
+            Some(next) if fuel(&next) <= *budget => {
+                low = next;
+                step = step.checked_add(&step).unwrap_or(step);
+            }
+            _ if step.is_one() => return low,
+            _ => step = step / M::from(2),
+        }
+    }
+}
+
+/// Every mass for which `fuel` is exactly `amount`, if there are any.
+fn needing<M: Mass>(amount: &M, fuel: impl Fn(&M) -> M) -> Option<RangeInclusive<M>> {
+    let last = largest_within(amount, &fuel);
+    let first = if amount.is_zero() {
+        M::zero()
+    } else {
+        // If even the largest mass needs less, none need exactly `amount`.
+        largest_within(&(amount.clone() - M::one()), &fuel).checked_add(&M::one())?
+    };
+
+    if first <= last && fuel(&last) == *amount {
+        Some(first..=last)
+    } else {
+        None
+    }
+}
+
+impl Formula {
+    /// The largest mass whose own fuel is at most `budget`.
+    pub fn max_mass<M: Mass>(&self, budget: &M) -> M {
+        largest_within(budget, |mass| self.fuel(mass))
+    }
+
+    /// The largest mass whose fuel, including the fuel for fuel, is at most
+    /// `budget`.
+    pub fn max_mass_total<M: Mass>(&self, budget: &M) -> M {
+        largest_within(budget, |mass| self.total_fuel(mass))
+    }
+
+    /// The masses whose own fuel is exactly `fuel`.
+    pub fn masses_needing<M: Mass>(&self, fuel: &M) -> Option<RangeInclusive<M>> {
+        needing(fuel, |mass| self.fuel(mass))
+    }
+
+    /// The masses whose fuel, including the fuel for fuel, is exactly
+    /// `total`. Some totals can't be reached by any mass.
+    pub fn masses_needing_total<M: Mass>(&self, total: &M) -> Option<RangeInclusive<M>> {
+        needing(total, |mass| self.total_fuel(mass))
+    }
+}
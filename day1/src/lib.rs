@@ -0,0 +1,47 @@
+pub mod fuel;
+pub mod inverse;
+pub mod manifest;
+
+use common::parse::{lines, number};
+use common::{ParseError, Solution};
+use fuel::Formula;
+
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    lines(input).map(|line| number(input, line)).collect()
+}
+
+/// Adds up the fuel for every mass, unless that is more than a `u64` holds.
+fn sum(input: &[u64], fuel: impl Fn(&u64) -> u64) -> Result<u64, String> {
+    input
+        .iter()
+        .try_fold(0u64, |sum, mass| sum.checked_add(fuel(mass)))
+        .ok_or_else(|| "the fuel adds up to more than 64 bits can hold".to_string())
+}
+
+pub fn part1(input: &[u64]) -> Result<u64, String> {
+    sum(input, |mass| Formula::ROCKET.fuel(mass))
+}
+
+pub fn part2(input: &[u64]) -> Result<u64, String> {
+    sum(input, |mass| Formula::ROCKET.total_fuel(mass))
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<u64>;
+    type Part1 = Result<u64, String>;
+    type Part2 = Result<u64, String>;
+
+    fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<u64>) -> Result<u64, String> {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<u64>) -> Result<u64, String> {
+        part2(input)
+    }
+}
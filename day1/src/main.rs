@@ -1,40 +1,167 @@
-use std::io::{self, BufRead};
-use std::iter;
+use clap::Parser;
+use day1::fuel::{Formula, Mass, Rounding};
+use day1::manifest::{self, Module};
+use num_bigint::BigUint;
+use std::fs;
+use std::io::{self, Read};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::process;
 
-fn calculate_fuel(mass: i32) -> i32 {
-    (mass / 3) - 2
+#[derive(Debug, Parser)]
+struct Options {
+    /// Read the modules from a file instead of stdin, as CSV (`name,mass`)
+    /// or JSON when it has that extension
+    #[arg(long, value_name = "PATH")]
+    manifest: Option<PathBuf>,
+
+    /// What each module's mass is divided by
+    #[arg(long, default_value_t = 3)]
+    divisor: u64,
+
+    /// What is taken away after dividing
+    #[arg(long, default_value_t = 2)]
+    offset: u64,
+
+    /// How to round after dividing: `down`, `up` or `nearest`
+    #[arg(long, default_value = "down")]
+    rounding: Rounding,
+
+    /// Allow masses and totals too large for 64 bits
+    #[arg(long)]
+    big: bool,
+
+    /// List the fuel needed by every module
+    #[arg(long)]
+    breakdown: bool,
+
+    /// Instead of fuelling modules, find the largest mass this much fuel
+    /// can lift
+    #[arg(long, value_name = "FUEL")]
+    budget: Option<String>,
 }
 
-fn part1(input: &[i32]) {
-    let answer: i32 = input.iter().map(|&mass| calculate_fuel(mass)).sum();
-    println!("part 1 = {}", answer);
+fn describe<M: Mass>(range: Option<RangeInclusive<M>>) -> String {
+    match range {
+        Some(range) => format!("{} to {}", range.start(), range.end()),
+        None => "none".to_string(),
+    }
 }
 
-fn part2(input: &[i32]) {
-    let answer: i32 = input
-        .iter()
-        .map(|&mass| {
-            let fuel = calculate_fuel(mass);
-            iter::successors(Some(fuel), |&prev_mass| {
-                let fuel = calculate_fuel(prev_mass);
-                Some(fuel)
-            })
-            .take_while(|fuel| fuel.is_positive())
-            .sum::<i32>()
-        })
-        .sum();
+/// `mass`, noting when it is only the largest because no larger one fits in
+/// 64 bits.
+fn largest<M: Mass>(mass: M, big: bool) -> String {
+    if !big && mass == M::from(u64::MAX) {
+        format!("{} (the most 64 bits can hold; try `--big`)", mass)
+    } else {
+        mass.to_string()
+    }
+}
 
-    println!("part 2 = {}", answer);
+fn invert<M: Mass>(formula: &Formula, budget: &str, big: bool) {
+    let budget: M = budget.parse().unwrap_or_else(|_| {
+        eprintln!(
+            "Invalid budget: expected a whole number, found `{}`",
+            budget
+        );
+        process::exit(2);
+    });
+
+    println!("largest mass = {}", largest(formula.max_mass(&budget), big));
+    println!(
+        "masses needing exactly that fuel = {}",
+        describe(formula.masses_needing(&budget))
+    );
+    println!(
+        "largest mass including fuel for fuel = {}",
+        largest(formula.max_mass_total(&budget), big)
+    );
+    println!(
+        "masses needing exactly that fuel including fuel for fuel = {}",
+        describe(formula.masses_needing_total(&budget))
+    );
+}
+
+fn solve<M: Mass>(options: &Options, formula: &Formula, input: &str) {
+    let modules: Vec<Module<M>> = match &options.manifest {
+        Some(path) => manifest::parse(path, input),
+        None => manifest::parse_masses(input),
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("Failed to parse input: {}", e);
+        process::exit(1);
+    });
+
+    let modules = manifest::breakdown(formula, &modules);
+    if options.breakdown {
+        let width = modules
+            .iter()
+            .map(|module| module.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        for module in &modules {
+            println!(
+                "{:width$}  mass {}  fuel {}  total fuel {}",
+                module.name,
+                module.mass,
+                module.fuel,
+                module.total_fuel,
+                width = width
+            );
+        }
+    }
+
+    let fuel = sum(modules.iter().map(|module| &module.fuel));
+    let total_fuel = sum(modules.iter().map(|module| &module.total_fuel));
+    println!("part 1 = {}", fuel);
+    println!("part 2 = {}", total_fuel);
+}
+
+/// Adds up `amounts`, exiting if they overflow.
+fn sum<'a, M: Mass + 'a>(mut amounts: impl Iterator<Item = &'a M>) -> M {
+    amounts
+        .try_fold(M::zero(), |sum, amount| sum.checked_add(amount))
+        .unwrap_or_else(|| {
+            eprintln!("The fuel adds up to more than 64 bits can hold; try `--big`");
+            process::exit(1);
+        })
 }
 
 fn main() {
-    let input = io::stdin()
-        .lock()
-        .lines()
-        .map(|line| line.expect("Failed to read data"))
-        .map(|line| line.parse().expect("Failed to parse number"))
-        .collect::<Vec<i32>>();
-
-    part1(&input);
-    part2(&input);
+    let options = Options::parse();
+    let formula =
+        Formula::new(options.divisor, options.offset, options.rounding).unwrap_or_else(|e| {
+            eprintln!("Invalid formula: {}", e);
+            process::exit(2);
+        });
+
+    if let Some(budget) = &options.budget {
+        if options.big {
+            invert::<BigUint>(&formula, budget, true);
+        } else {
+            invert::<u64>(&formula, budget, false);
+        }
+        return;
+    }
+
+    let input = match &options.manifest {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("{}: {}", path.display(), e);
+            process::exit(1);
+        }),
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .lock()
+                .read_to_string(&mut input)
+                .expect("Failed to read data");
+            input
+        }
+    };
+
+    if options.big {
+        solve::<BigUint>(&options, &formula, &input);
+    } else {
+        solve::<u64>(&options, &formula, &input);
+    }
 }
@@ -0,0 +1,147 @@
+//! Lists of spacecraft modules to fuel, given either as one mass per line
+//! like the puzzle input, as CSV with `name,mass` rows, or as a JSON array
+//! of `{"name": ..., "mass": ...}` objects. Masses too large for a JSON
+//! number can be written as strings.
+
+use crate::fuel::{Formula, Mass};
+use common::parse::{lines, number, split_once};
+use common::ParseError;
+use serde::de::{self, Deserializer, Visitor};
+use serde::Deserialize;
+use std::fmt;
+use std::marker::PhantomData;
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Module<M> {
+    pub name: String,
+    pub mass: M,
+}
+
+/// The fuel one module needs on its own and once the fuel's own mass is
+/// accounted for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModuleFuel<M> {
+    pub name: String,
+    pub mass: M,
+    pub fuel: M,
+    pub total_fuel: M,
+}
+
+pub fn breakdown<M: Mass>(formula: &Formula, modules: &[Module<M>]) -> Vec<ModuleFuel<M>> {
+    modules
+        .iter()
+        .map(|module| ModuleFuel {
+            name: module.name.clone(),
+            mass: module.mass.clone(),
+            fuel: formula.fuel(&module.mass),
+            total_fuel: formula.total_fuel(&module.mass),
+        })
+        .collect()
+}
+
+/// One mass per line, naming the modules by their line.
+pub fn parse_masses<M: Mass>(input: &str) -> Result<Vec<Module<M>>, ParseError> {
+    lines(input)
+        .enumerate()
+        .map(|(i, line)| {
+            Ok(Module {
+                name: format!("module {}", i + 1),
+                mass: number(input, line)?,
+            })
+        })
+        .collect()
+}
+
+pub fn parse_csv<M: Mass>(input: &str) -> Result<Vec<Module<M>>, ParseError> {
+    let mut modules = Vec::new();
+    for (i, line) in lines(input).enumerate() {
+        let (name, mass) = split_once(input, line, ',')?;
+        let (name, mass) = (name.trim(), mass.trim());
+        if i == 0 && name.eq_ignore_ascii_case("name") && mass.eq_ignore_ascii_case("mass") {
+            continue;
+        }
+        if name.is_empty() {
+            return Err(ParseError::new(input, line, "expected a module name"));
+        }
+
+        modules.push(Module {
+            name: name.to_string(),
+            mass: number(input, mass)?,
+        });
+    }
+
+    Ok(modules)
+}
+
+#[derive(Deserialize)]
+#[serde(bound = "M: Mass")]
+struct Entry<M> {
+    name: String,
+    mass: MassField<M>,
+}
+
+struct MassField<M>(M);
+
+impl<'de, M: Mass> Deserialize<'de> for MassField<M> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<MassField<M>, D::Error> {
+        struct MassVisitor<M>(PhantomData<M>);
+
+        impl<'de, M: Mass> Visitor<'de> for MassVisitor<M> {
+            type Value = MassField<M>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a mass as a whole number or a string of digits")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<MassField<M>, E> {
+                Ok(MassField(M::from(value)))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<MassField<M>, E> {
+                value
+                    .parse()
+                    .map(MassField)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+            }
+        }
+
+        deserializer.deserialize_any(MassVisitor(PhantomData))
+    }
+}
+
+pub fn parse_json<M: Mass>(input: &str) -> Result<Vec<Module<M>>, ParseError> {
+    let entries: Vec<Entry<M>> = serde_json::from_str(input).map_err(|e| {
+        // serde_json counts lines and columns from 1 as well, and appends
+        // them to its message.
+        let suffix = format!(" at line {} column {}", e.line(), e.column());
+        let message = e.to_string();
+        let line = input.lines().nth(e.line().saturating_sub(1)).unwrap_or("");
+        ParseError {
+            line: e.line(),
+            column: e.column(),
+            text: line.trim().to_string(),
+            message: message
+                .strip_suffix(&suffix)
+                .unwrap_or(&message)
+                .to_string(),
+        }
+    })?;
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| Module {
+            name: entry.name,
+            mass: entry.mass.0,
+        })
+        .collect())
+}
+
+/// Picks the format from the extension of the file `input` was read from.
+pub fn parse<M: Mass>(path: &Path, input: &str) -> Result<Vec<Module<M>>, ParseError> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("csv") => parse_csv(input),
+        Some("json") => parse_json(input),
+        _ => parse_masses(input),
+    }
+}
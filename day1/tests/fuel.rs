@@ -0,0 +1,58 @@
+use common::Answer;
+use day1::fuel::{Formula, Rounding};
+use num_bigint::BigUint;
+use proptest::prelude::*;
+
+fn rounding() -> impl Strategy<Value = Rounding> {
+    prop_oneof![
+        Just(Rounding::Down),
+        Just(Rounding::Up),
+        Just(Rounding::Nearest)
+    ]
+}
+
+proptest! {
+    #[test]
+    fn any_u64_mass_agrees_with_big_masses(
+        divisor in 2u64..,
+        offset in 1u64..10,
+        rounding in rounding(),
+        mass in any::<u64>(),
+    ) {
+        let formula = Formula::new(divisor, offset, rounding).unwrap();
+        prop_assert_eq!(
+            BigUint::from(formula.fuel(&mass)),
+            formula.fuel(&BigUint::from(mass))
+        );
+        prop_assert_eq!(
+            BigUint::from(formula.total_fuel(&mass)),
+            formula.total_fuel(&BigUint::from(mass))
+        );
+    }
+}
+
+#[test]
+fn rounds_halves_up() {
+    let formula = Formula::new(4, 1, Rounding::Nearest).unwrap();
+    let fuel = [5u64, 6, 7, 9, 10]
+        .iter()
+        .map(|mass| formula.fuel(mass))
+        .collect::<Vec<u64>>();
+    assert_eq!(fuel, [0, 1, 1, 1, 2]);
+    assert_eq!(formula.fuel(&u64::MAX), u64::MAX / 4);
+}
+
+#[test]
+fn answers_totals_too_large_for_64_bits_with_an_error() {
+    let masses = [u64::MAX; 2];
+    assert_eq!(day1::part1(&masses), Ok(12_297_829_382_473_034_406));
+    assert_eq!(
+        Answer::from(day1::part2(&masses)),
+        Answer::Number(18_446_744_073_709_551_338)
+    );
+
+    let masses = [u64::MAX; 4];
+    let error = "the fuel adds up to more than 64 bits can hold".to_string();
+    assert_eq!(day1::part1(&masses), Err(error.clone()));
+    assert_eq!(Answer::from(day1::part2(&masses)), Answer::Error(error));
+}
@@ -0,0 +1,71 @@
+use day1::fuel::{Formula, Rounding};
+use num_bigint::BigUint;
+use proptest::prelude::*;
+
+fn formula() -> impl Strategy<Value = Formula> {
+    let rounding = prop_oneof![
+        Just(Rounding::Down),
+        Just(Rounding::Up),
+        Just(Rounding::Nearest)
+    ];
+    (2u64..10, 1u64..10, rounding)
+        .prop_map(|(divisor, offset, rounding)| Formula::new(divisor, offset, rounding).unwrap())
+}
+
+proptest! {
+    #[test]
+    fn max_mass_is_the_largest_within_budget(formula in formula(), budget in 0u64..1_000_000) {
+        let mass = formula.max_mass(&budget);
+        prop_assert!(formula.fuel(&mass) <= budget);
+        prop_assert!(formula.fuel(&(mass + 1)) > budget);
+    }
+
+    #[test]
+    fn max_mass_total_is_the_largest_within_budget(
+        formula in formula(),
+        budget in 0u64..1_000_000,
+    ) {
+        let mass = formula.max_mass_total(&budget);
+        prop_assert!(formula.total_fuel(&mass) <= budget);
+        prop_assert!(formula.total_fuel(&(mass + 1)) > budget);
+    }
+
+    #[test]
+    fn masses_needing_inverts_fuel(formula in formula(), mass in 0u64..1_000_000) {
+        let fuel = formula.fuel(&mass);
+        let range = formula.masses_needing(&fuel).expect("at least this mass");
+        prop_assert!(range.contains(&mass));
+        prop_assert_eq!(formula.fuel(range.start()), fuel);
+        prop_assert_eq!(formula.fuel(range.end()), fuel);
+        if *range.start() > 0 {
+            prop_assert!(formula.fuel(&(range.start() - 1)) < fuel);
+        }
+        prop_assert!(formula.fuel(&(range.end() + 1)) > fuel);
+    }
+
+    #[test]
+    fn masses_needing_total_inverts_total_fuel(formula in formula(), mass in 0u64..1_000_000) {
+        let total = formula.total_fuel(&mass);
+        let range = formula.masses_needing_total(&total).expect("at least this mass");
+        prop_assert!(range.contains(&mass));
+        prop_assert_eq!(formula.total_fuel(range.end()), total);
+        prop_assert!(formula.total_fuel(&(range.end() + 1)) > total);
+    }
+
+    #[test]
+    fn big_masses_agree_with_u64(formula in formula(), budget in 0u64..1_000_000_000) {
+        let mass = formula.max_mass_total(&budget);
+        let big = formula.max_mass_total(&BigUint::from(budget));
+        prop_assert_eq!(BigUint::from(mass), big);
+    }
+}
+
+#[test]
+fn budgets_beyond_every_mass_stop_at_the_largest() {
+    let formula = Formula::new(3, 2, Rounding::Down).unwrap();
+    let budget = 10_000_000_000_000_000_000u64;
+    assert_eq!(formula.max_mass(&budget), u64::MAX);
+    assert_eq!(formula.max_mass_total(&budget), u64::MAX);
+    assert_eq!(formula.masses_needing(&budget), None);
+    assert_eq!(formula.masses_needing_total(&budget), None);
+}
@@ -0,0 +1,116 @@
+use approx::relative_eq;
+use common::parse::lines;
+use common::{ParseError, Solution};
+use geometry::Point;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::{FRAC_PI_2, PI};
+
+fn is_blocked(segment: &[Point], points: &HashSet<Point>) -> bool {
+    let a = *segment.first().unwrap();
+    let b = *segment.last().unwrap();
+    let ab = a.distance(b);
+
+    points.iter().filter(|&c| *c != a && *c != b).any(|&c| {
+        let ac = a.distance(c);
+        let bc = b.distance(c);
+        relative_eq!(ac + bc, ab)
+    })
+}
+
+fn find_station(asteroids: &HashSet<Point>) -> (Point, usize) {
+    let counts: HashMap<Point, usize> = asteroids
+        .iter()
+        .cloned()
+        .combinations(2)
+        .filter(|segment| !is_blocked(segment, asteroids))
+        .flatten()
+        .fold(HashMap::new(), |mut acc, point| {
+            *acc.entry(point).or_insert(0) += 1;
+            acc
+        });
+
+    counts.into_iter().max_by_key(|&(_, count)| count).unwrap()
+}
+
+pub fn parse(input: &str) -> Result<HashSet<Point>, ParseError> {
+    let mut asteroids = HashSet::new();
+    for (y, line) in lines(input).enumerate() {
+        for (x, (offset, ch)) in line.char_indices().enumerate() {
+            match ch {
+                '#' => {
+                    asteroids.insert(Point::new(x as i64, y as i64));
+                }
+                '.' => {}
+                _ => {
+                    let text = &line[offset..offset + ch.len_utf8()];
+                    return Err(ParseError::new(input, text, "expected `#` or `.`"));
+                }
+            }
+        }
+    }
+
+    Ok(asteroids)
+}
+
+pub fn part1(input: &HashSet<Point>) -> usize {
+    let (_, answer) = find_station(input);
+    answer
+}
+
+pub fn part2(input: &HashSet<Point>) -> i64 {
+    let (station, _) = find_station(input);
+    let targets = input
+        .iter()
+        .filter(|&p| *p != station)
+        .map(|&p| {
+            let delta = p - station;
+            let (x, y) = (delta.x as f64, delta.y as f64);
+            let mut theta = y.atan2(x) + FRAC_PI_2;
+            if theta < 0.0 {
+                theta += 2.0 * PI;
+            }
+
+            (p, theta)
+        })
+        .sorted_by(|&(a, _), &(b, _)| {
+            station
+                .distance(a)
+                .partial_cmp(&station.distance(b))
+                .unwrap()
+        })
+        .sorted_by(|&(_, a), &(_, b)| a.partial_cmp(&b).unwrap())
+        .group_by(|&(_, theta)| theta)
+        .into_iter()
+        .flat_map(|(_, group)| {
+            group
+                .enumerate()
+                .map(|(i, (p, theta))| (p, (f64::from(i as u16) * PI * 2.0) + theta))
+                .collect::<Vec<(Point, f64)>>()
+        })
+        .sorted_by(|&(_, a), &(_, b)| a.partial_cmp(&b).unwrap())
+        .collect::<Vec<(Point, f64)>>();
+
+    let (target, _) = targets[199];
+    target.x * 100 + target.y
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = HashSet<Point>;
+    type Part1 = usize;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<HashSet<Point>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &HashSet<Point>) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &HashSet<Point>) -> i64 {
+        part2(input)
+    }
+}
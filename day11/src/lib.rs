@@ -0,0 +1,139 @@
+use common::{Grid, ParseError, Solution};
+use geometry::{Direction, Point, SparseGrid};
+use intcode::{Computer, ComputerResult};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Color {
+    White,
+    Black,
+}
+
+impl From<i64> for Color {
+    fn from(source: i64) -> Color {
+        match source {
+            0 => Color::Black,
+            1 => Color::White,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<Color> for i64 {
+    fn from(source: Color) -> i64 {
+        match source {
+            Color::Black => 0,
+            Color::White => 1,
+        }
+    }
+}
+
+struct Robot {
+    computer: Computer,
+    direction: Direction,
+    location: Point,
+}
+
+impl Robot {
+    fn new(memory: &[i64]) -> Robot {
+        Robot {
+            computer: Computer::new(memory),
+            direction: Direction::Up,
+            location: Point::ORIGIN,
+        }
+    }
+
+    fn is_halted(&self) -> bool {
+        self.computer.halted
+    }
+
+    fn next_color(&mut self, current: Color) -> Option<Color> {
+        self.computer.inputs.push_back(current.into());
+        match self.computer.run().expect("a working program") {
+            ComputerResult::Output(color_value) => Some(color_value.into()),
+            ComputerResult::Halted => None,
+            ComputerResult::NeedInput => unreachable!("a new color"),
+        }
+    }
+
+    fn move_forward(&mut self) {
+        let direction_value = match self.computer.run().expect("a working program") {
+            ComputerResult::Output(direction_value) => direction_value,
+            _ => unreachable!("a new direction"),
+        };
+        self.direction = match direction_value {
+            0 => self.direction.turn_left(),
+            1 => self.direction.turn_right(),
+            _ => unreachable!(),
+        };
+
+        self.location += self.direction.offset();
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    intcode::parse_program(input)
+}
+
+pub fn part1(memory: &[i64]) -> usize {
+    let mut robot = Robot::new(memory);
+    let mut grid = SparseGrid::new();
+
+    loop {
+        if robot.is_halted() {
+            break;
+        }
+
+        let current_color = *grid.get(robot.location).unwrap_or(&Color::Black);
+        if let Some(next_color) = robot.next_color(current_color) {
+            *grid.entry(robot.location).or_insert(Color::Black) = next_color;
+            robot.move_forward();
+        } else {
+            break;
+        }
+    }
+
+    grid.len()
+}
+
+pub fn part2(memory: &[i64]) -> Grid {
+    let mut robot = Robot::new(memory);
+    let mut grid = SparseGrid::new();
+    grid.insert(Point::ORIGIN, Color::White);
+
+    loop {
+        if robot.is_halted() {
+            break;
+        }
+
+        let current_color = *grid.get(robot.location).unwrap_or(&Color::Black);
+        if let Some(next_color) = robot.next_color(current_color) {
+            *grid.entry(robot.location).or_insert(Color::Black) = next_color;
+            robot.move_forward();
+        } else {
+            break;
+        }
+    }
+
+    grid.to_dense(Color::Black)
+        .map(|&color| color == Color::White)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<i64>;
+    type Part1 = usize;
+    type Part2 = Grid;
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<i64>) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<i64>) -> Grid {
+        part2(input)
+    }
+}
@@ -0,0 +1,219 @@
+use common::parse::{lines, number, split_once};
+use common::{ParseError, Solution};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+type Vector = (i64, i64, i64);
+
+#[derive(Copy, Clone)]
+pub struct Moon {
+    position: Vector,
+    velocity: Vector,
+}
+
+impl Moon {
+    fn potenial_energy(self) -> i64 {
+        self.position.0.abs() + self.position.1.abs() + self.position.2.abs()
+    }
+
+    fn kinetic_energy(self) -> i64 {
+        self.velocity.0.abs() + self.velocity.1.abs() + self.velocity.2.abs()
+    }
+
+    fn total_energy(self) -> i64 {
+        self.potenial_energy() * self.kinetic_energy()
+    }
+
+    fn accelerate(&mut self, delta: Vector) {
+        self.velocity.0 += delta.0;
+        self.velocity.1 += delta.1;
+        self.velocity.2 += delta.2;
+    }
+
+    fn update_position(&mut self) {
+        self.position.0 += self.velocity.0;
+        self.position.1 += self.velocity.1;
+        self.position.2 += self.velocity.2;
+    }
+}
+
+fn delta(a: i64, b: i64) -> i64 {
+    match a.cmp(&b) {
+        Ordering::Less => 1,
+        Ordering::Greater => -1,
+        Ordering::Equal => 0,
+    }
+}
+
+fn calculate_accleration(moons: &mut [Moon], index: usize) {
+    let length = moons.len();
+    let delta = (0..length)
+        .filter(|&j| j != index)
+        .map(|j| {
+            let (ax, ay, az) = moons[index].position;
+            let (bx, by, bz) = moons[j].position;
+
+            (delta(ax, bx), delta(ay, by), delta(az, bz))
+        })
+        .fold(Vector::default(), |(x, y, z), (dx, dy, dz)| {
+            (x + dx, y + dy, z + dz)
+        });
+
+    moons[index].accelerate(delta);
+}
+
+fn parse_moon(input: &str, line: &str) -> Result<Moon, ParseError> {
+    let coordinates = line
+        .strip_prefix('<')
+        .and_then(|rest| rest.strip_suffix('>'))
+        .ok_or_else(|| ParseError::new(input, line, "expected `<x=.., y=.., z=..>`"))?;
+
+    let mut parts = coordinates.split(',').map(str::trim);
+    let mut position = [0; 3];
+    for (axis, name) in ["x", "y", "z"].iter().enumerate() {
+        let part = parts.next().ok_or_else(|| {
+            ParseError::new(input, line, format!("expected a `{}` coordinate", name))
+        })?;
+        let (label, value) = split_once(input, part, '=')?;
+        if label != *name {
+            return Err(ParseError::new(
+                input,
+                label,
+                format!("expected `{}`", name),
+            ));
+        }
+
+        position[axis] = number(input, value)?;
+    }
+
+    if let Some(extra) = parts.next() {
+        return Err(ParseError::new(
+            input,
+            extra,
+            "expected only three coordinates",
+        ));
+    }
+
+    Ok(Moon {
+        position: (position[0], position[1], position[2]),
+        velocity: Vector::default(),
+    })
+}
+
+pub fn parse(input: &str) -> Result<Vec<Moon>, ParseError> {
+    lines(input).map(|line| parse_moon(input, line)).collect()
+}
+
+pub fn part1(input: &[Moon]) -> i64 {
+    let mut moons = input.to_vec();
+    let length = moons.len();
+
+    for _ in 0..1000 {
+        for i in 0..length {
+            calculate_accleration(&mut moons, i);
+        }
+
+        for moon in moons.iter_mut() {
+            moon.update_position();
+        }
+    }
+
+    moons.iter().map(|m| m.total_energy()).sum()
+}
+
+pub fn part2(input: &[Moon]) -> u64 {
+    let length = input.len();
+    let mut times = (0u64, 0, 0);
+
+    let mut moons = input.to_vec();
+    let mut x_positions = HashSet::new();
+    for t in 0.. {
+        for i in 0..length {
+            calculate_accleration(&mut moons, i);
+        }
+
+        for moon in moons.iter_mut() {
+            moon.update_position();
+        }
+
+        let positions = moons
+            .iter()
+            .map(|moon| (moon.position.0, moon.velocity.0))
+            .collect::<Vec<(i64, i64)>>();
+        if x_positions.contains(&positions) {
+            times.0 = t;
+            break;
+        } else {
+            x_positions.insert(positions);
+        }
+    }
+
+    let mut moons = input.to_vec();
+    let mut y_positions = HashSet::new();
+    for t in 0.. {
+        for i in 0..length {
+            calculate_accleration(&mut moons, i);
+        }
+
+        for moon in moons.iter_mut() {
+            moon.update_position();
+        }
+
+        let positions = moons
+            .iter()
+            .map(|moon| (moon.position.1, moon.velocity.1))
+            .collect::<Vec<(i64, i64)>>();
+        if y_positions.contains(&positions) {
+            times.1 = t;
+            break;
+        } else {
+            y_positions.insert(positions);
+        }
+    }
+
+    let mut moons = input.to_vec();
+    let mut z_positions = HashSet::new();
+    for t in 0.. {
+        for i in 0..length {
+            calculate_accleration(&mut moons, i);
+        }
+
+        for moon in moons.iter_mut() {
+            moon.update_position();
+        }
+
+        let positions = moons
+            .iter()
+            .map(|moon| (moon.position.2, moon.velocity.2))
+            .collect::<Vec<(i64, i64)>>();
+        if z_positions.contains(&positions) {
+            times.2 = t;
+            break;
+        } else {
+            z_positions.insert(positions);
+        }
+    }
+
+    let a = num_integer::lcm(times.0, times.1);
+    num_integer::lcm(a, times.2)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Moon>;
+    type Part1 = i64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Moon>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Moon>) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Moon>) -> u64 {
+        part2(input)
+    }
+}
@@ -0,0 +1,162 @@
+use common::{ParseError, Solution};
+use geometry::{DenseGrid, Direction, Point, SparseGrid};
+use intcode::{Computer, ComputerResult};
+use std::cmp::Ordering;
+
+const PADDLE_Y: i64 = 21;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Tile {
+    Blank = 0,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+impl From<i64> for Tile {
+    fn from(source: i64) -> Self {
+        match source {
+            0 => Tile::Blank,
+            1 => Tile::Wall,
+            2 => Tile::Block,
+            3 => Tile::Paddle,
+            4 => Tile::Ball,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum PlayResult {
+    Tile(Point, Tile),
+    Score(i64),
+    NeedInput,
+    Halted,
+}
+
+fn play_game(game: &mut Computer) -> PlayResult {
+    match game.run().expect("a working program") {
+        ComputerResult::Halted => PlayResult::Halted,
+        ComputerResult::NeedInput => PlayResult::NeedInput,
+        ComputerResult::Output(x) => {
+            let y = match game.run().expect("a working program") {
+                ComputerResult::Halted => unreachable!(),
+                ComputerResult::NeedInput => unreachable!(),
+                ComputerResult::Output(y) => y,
+            };
+
+            let value = match game.run().expect("a working program") {
+                ComputerResult::Halted => unreachable!(),
+                ComputerResult::NeedInput => unreachable!(),
+                ComputerResult::Output(value) => value,
+            };
+
+            if x == -1 && y == 0 {
+                PlayResult::Score(value)
+            } else {
+                PlayResult::Tile(Point::new(x, y), value.into())
+            }
+        }
+    }
+}
+
+fn find_paddle(grid: &SparseGrid<Tile>) -> Option<Point> {
+    grid.iter()
+        .find(|&(_, t)| *t == Tile::Paddle)
+        .map(|(p, _)| p)
+}
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    intcode::parse_program(input)
+}
+
+/// The arcade screen as drawn before the game is played.
+pub fn screen(memory: &[i64]) -> DenseGrid<Tile> {
+    let mut grid = SparseGrid::new();
+    let mut game = Computer::new(memory);
+
+    while let PlayResult::Tile(point, tile) = play_game(&mut game) {
+        grid.insert(point, tile);
+    }
+
+    grid.to_dense(Tile::Blank)
+}
+
+pub fn part1(memory: &[i64]) -> usize {
+    screen(memory)
+        .iter()
+        .filter(|&(_, t)| *t == Tile::Block)
+        .count()
+}
+
+pub fn part2(memory: &[i64]) -> i64 {
+    let mut grid = SparseGrid::new();
+    let mut game = Computer::new(memory);
+    let mut score = 0;
+    let mut ball_position: Option<Point> = None;
+    let mut x_target = 0;
+
+    game.memory[0] = 2;
+
+    loop {
+        match play_game(&mut game) {
+            PlayResult::Tile(point, tile) => {
+                grid.insert(point, tile);
+
+                if tile == Tile::Ball {
+                    if let Some(old_point) = ball_position {
+                        x_target = if old_point.y < point.y {
+                            let m = (old_point.y - point.y) / (old_point.x - point.x);
+                            let b = point.y - m * point.x;
+                            (PADDLE_Y - b) / m
+                        } else {
+                            point.x
+                        };
+
+                        let paddle = find_paddle(&grid).unwrap();
+                        if point == paddle + Direction::Up.offset() {
+                            x_target -= 1;
+                        }
+                    }
+
+                    ball_position = Some(point);
+                }
+            }
+            PlayResult::Score(value) => {
+                score = value;
+            }
+            PlayResult::NeedInput => {
+                let paddle = find_paddle(&grid).unwrap();
+                match paddle.x.cmp(&x_target) {
+                    Ordering::Greater => game.inputs.push_back(-1),
+                    Ordering::Less => game.inputs.push_back(1),
+                    Ordering::Equal => game.inputs.push_back(0),
+                }
+            }
+            PlayResult::Halted => break,
+        }
+    }
+
+    score
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<i64>;
+    type Part1 = usize;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<i64>) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<i64>) -> i64 {
+        part2(input)
+    }
+}
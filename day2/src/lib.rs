@@ -0,0 +1,75 @@
+use common::parse::comma_separated;
+use common::{ParseError, Solution};
+
+const PART2_RESULT: usize = 19_690_720;
+
+fn execute_intcode(noun: usize, verb: usize, memory: &[usize]) -> usize {
+    let mut memory = memory.to_vec();
+
+    memory[1] = noun;
+    memory[2] = verb;
+
+    let mut ip = 0;
+    loop {
+        match memory[ip] {
+            1 => {
+                let value = memory[memory[ip + 1]] + memory[memory[ip + 2]];
+                let address = memory[ip + 3];
+                memory[address] = value;
+                ip += 4;
+            }
+            2 => {
+                let value = memory[memory[ip + 1]] * memory[memory[ip + 2]];
+                let address = memory[ip + 3];
+                memory[address] = value;
+                ip += 4;
+            }
+            99 => break,
+            _ => unreachable!(),
+        }
+    }
+
+    memory[0]
+}
+
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    comma_separated(input)
+}
+
+pub fn part1(memory: &[usize]) -> usize {
+    execute_intcode(12, 2, memory)
+}
+
+pub fn part2(memory: &[usize]) -> usize {
+    for noun in 0..=99 {
+        for verb in 0..=99 {
+            let result = execute_intcode(noun, verb, memory);
+            if result == PART2_RESULT {
+                let answer = 100 * noun + verb;
+                return answer;
+            }
+        }
+    }
+
+    unreachable!("no noun and verb produce {}", PART2_RESULT)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<usize>) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<usize>) -> usize {
+        part2(input)
+    }
+}
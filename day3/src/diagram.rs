@@ -0,0 +1,280 @@
+//! Draws wires along with where they cross: in the terminal with box
+//! drawing characters, scaled down to fit when the wires are long, or as
+//! SVG for when they are too large to make out that way.
+
+use crate::network::{pairs, Pair};
+use crate::wire::Wire;
+use geometry::{Bounds, Direction, Point, SparseGrid};
+use std::fmt::Write;
+
+const MAX_WIDTH: i64 = 160;
+const MAX_HEIGHT: i64 = 60;
+
+const ANSI_COLORS: [&str; 6] = [
+    "\x1b[31m", "\x1b[32m", "\x1b[34m", "\x1b[33m", "\x1b[35m", "\x1b[36m",
+];
+const ANSI_MARKER: &str = "\x1b[1;97m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+const SVG_COLORS: [&str; 6] = [
+    "#d62728", "#2ca02c", "#1f77b4", "#ff7f0e", "#9467bd", "#17becf",
+];
+
+/// The points worth marking on a diagram.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Markers {
+    pub intersections: Vec<Point>,
+    /// The crossing closest to the origin.
+    pub closest: Option<Point>,
+    /// The crossing reached in the fewest combined steps.
+    pub fewest_steps: Option<Point>,
+}
+
+impl Markers {
+    /// Marks the ends of everything shared between any pair of wires, and
+    /// the best crossings of the first two wires as the puzzle asks.
+    pub fn new(wires: &[Wire]) -> Markers {
+        let pairs = pairs(wires);
+        let mut intersections = pairs
+            .iter()
+            .flat_map(|pair| &pair.intersections)
+            .flat_map(|intersection| vec![intersection.from, intersection.to])
+            .filter(|&point| point != Point::ORIGIN)
+            .collect::<Vec<Point>>();
+        intersections.sort();
+        intersections.dedup();
+
+        let first = pairs
+            .iter()
+            .find(|pair| pair.first == 0 && pair.second == 1);
+        Markers {
+            intersections,
+            closest: first.and_then(Pair::closest),
+            fewest_steps: first.and_then(Pair::fewest_steps).map(|(point, _)| point),
+        }
+    }
+}
+
+fn bounds(wires: &[Wire]) -> Bounds {
+    let points = wires
+        .iter()
+        .flat_map(|wire| &wire.segments)
+        .flat_map(|segment| vec![segment.start, segment.end()]);
+    Bounds::containing(points).unwrap_or_else(|| Bounds::new(Point::ORIGIN))
+}
+
+/// Which neighbours a cell of the terminal diagram joins up with, whether
+/// a wire runs through it diagonally, as `╲` or `╱`, and the wires passing
+/// through it.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Cell {
+    links: [bool; 4],
+    slants: [bool; 2],
+    wires: Vec<usize>,
+}
+
+fn link_index(direction: Direction) -> usize {
+    match direction {
+        Direction::Up => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Right => 3,
+    }
+}
+
+impl Cell {
+    fn visit(&mut self, wire: usize) {
+        if !self.wires.contains(&wire) {
+            self.wires.push(wire);
+        }
+    }
+
+    /// Joins this cell to the one `offset` away.
+    fn link(&mut self, offset: Point) {
+        match Direction::ALL
+            .iter()
+            .find(|direction| direction.offset() == offset)
+        {
+            Some(&direction) => self.links[link_index(direction)] = true,
+            None => self.slants[(offset.x != offset.y) as usize] = true,
+        }
+    }
+
+    fn symbol(&self) -> char {
+        match self.links {
+            [false, false, false, false] => match self.slants {
+                [true, true] => '╳',
+                [true, false] => '╲',
+                [false, true] => '╱',
+                [false, false] => '·',
+            },
+            [true, true, false, false]
+            | [true, false, false, false]
+            | [false, true, false, false] => '│',
+            [false, false, true, true]
+            | [false, false, true, false]
+            | [false, false, false, true] => '─',
+            [false, true, false, true] => '┌',
+            [false, true, true, false] => '┐',
+            [true, false, false, true] => '└',
+            [true, false, true, false] => '┘',
+            [true, true, false, true] => '├',
+            [true, true, true, false] => '┤',
+            [false, true, true, true] => '┬',
+            [true, false, true, true] => '┴',
+            [true, true, true, true] => '┼',
+        }
+    }
+}
+
+/// How many units of the wires each character covers so the diagram fits.
+pub fn terminal_scale(wires: &[Wire]) -> i64 {
+    let bounds = bounds(wires);
+    let width = (bounds.width() as i64 + MAX_WIDTH - 1) / MAX_WIDTH;
+    let height = (bounds.height() as i64 + MAX_HEIGHT - 1) / MAX_HEIGHT;
+    width.max(height).max(1)
+}
+
+/// Draws the wires with box drawing characters in a color per wire. Shared
+/// cells are drawn in white, `×` marks intersections, `o` the origin, `D`
+/// the closest crossing and `S` the one reached in the fewest steps.
+pub fn terminal(wires: &[Wire], markers: &Markers) -> String {
+    let scale = terminal_scale(wires);
+    let cell = |point: Point| Point::new(point.x.div_euclid(scale), point.y.div_euclid(scale));
+
+    let mut canvas: SparseGrid<Cell> = SparseGrid::new();
+    canvas.entry(Point::ORIGIN).or_default();
+    for (wire, segments) in wires.iter().map(|wire| &wire.segments).enumerate() {
+        for segment in segments {
+            // Look at the segment once per cell it passes through.
+            let length = segment.length as i64;
+            let samples = (0..length).step_by(scale as usize).chain(Some(length));
+            let mut cells =
+                samples.map(|step| cell(segment.start + segment.direction.offset() * step));
+
+            let mut previous = cells.next().expect("the start");
+            canvas.entry(previous).or_default().visit(wire);
+            for position in cells {
+                if position == previous {
+                    continue;
+                }
+                canvas
+                    .entry(previous)
+                    .or_default()
+                    .link(position - previous);
+                let next = canvas.entry(position).or_default();
+                next.link(previous - position);
+                next.visit(wire);
+                previous = position;
+            }
+        }
+    }
+
+    let marker = |point: Point| {
+        if point == Point::ORIGIN {
+            Some('o')
+        } else if markers.closest.map(cell) == Some(point) {
+            Some('D')
+        } else if markers.fewest_steps.map(cell) == Some(point) {
+            Some('S')
+        } else if markers
+            .intersections
+            .iter()
+            .any(|&marked| cell(marked) == point)
+        {
+            Some('×')
+        } else {
+            None
+        }
+    };
+
+    let bounds = canvas.bounds().expect("the origin is drawn");
+    let mut text = String::new();
+    if scale > 1 {
+        writeln!(text, "each character is {0}x{0}", scale).unwrap();
+    }
+    for y in bounds.min.y..=bounds.max.y {
+        for x in bounds.min.x..=bounds.max.x {
+            let point = Point::new(x, y);
+            match (marker(point), canvas.get(point)) {
+                (Some(symbol), _) => {
+                    write!(text, "{}{}{}", ANSI_MARKER, symbol, ANSI_RESET).unwrap()
+                }
+                (None, Some(cell)) if cell.wires.len() == 1 => {
+                    let color = ANSI_COLORS[cell.wires[0] % ANSI_COLORS.len()];
+                    write!(text, "{}{}{}", color, cell.symbol(), ANSI_RESET).unwrap()
+                }
+                (None, Some(cell)) if !cell.wires.is_empty() => text.push(cell.symbol()),
+                _ => text.push(' '),
+            }
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Draws the wires as SVG, in their own coordinates with `y` growing
+/// downwards as in the puzzle's pictures.
+pub fn svg(wires: &[Wire], markers: &Markers) -> String {
+    let bounds = bounds(wires);
+    let extent = bounds.width().max(bounds.height()) as f64;
+    let padding = (extent / 20.0).max(2.0);
+    let radius = (extent / 150.0).max(0.2);
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="1000" height="{:.0}">"#,
+        bounds.min.x as f64 - padding,
+        bounds.min.y as f64 - padding,
+        bounds.width() as f64 + 2.0 * padding,
+        bounds.height() as f64 + 2.0 * padding,
+        1000.0 * (bounds.height() as f64 + 2.0 * padding) / (bounds.width() as f64 + 2.0 * padding),
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"  <rect x="{}" y="{}" width="100%" height="100%" fill="white"/>"#,
+        bounds.min.x as f64 - padding,
+        bounds.min.y as f64 - padding
+    )
+    .unwrap();
+
+    for (wire, segments) in wires.iter().map(|wire| &wire.segments).enumerate() {
+        let points = IntoIterator::into_iter([Point::ORIGIN])
+            .chain(segments.iter().map(|segment| segment.end()))
+            .map(|point| format!("{},{}", point.x, point.y))
+            .collect::<Vec<String>>()
+            .join(" ");
+        writeln!(
+            svg,
+            r#"  <polyline points="{}" fill="none" stroke="{}" stroke-width="1.5" vector-effect="non-scaling-stroke"><title>wire {}</title></polyline>"#,
+            points,
+            SVG_COLORS[wire % SVG_COLORS.len()],
+            wire + 1
+        )
+        .unwrap();
+    }
+
+    let mut circle = |point: Point, radius: f64, fill: &str, title: &str| {
+        writeln!(
+            svg,
+            r#"  <circle cx="{}" cy="{}" r="{}" fill="{}"><title>{} {}</title></circle>"#,
+            point.x, point.y, radius, fill, title, point
+        )
+        .unwrap();
+    };
+    for &point in &markers.intersections {
+        circle(point, radius, "black", "intersection");
+    }
+    circle(Point::ORIGIN, radius * 1.5, "gray", "origin");
+    if let Some(point) = markers.closest {
+        circle(point, radius * 2.0, "gold", "closest");
+    }
+    if let Some(point) = markers.fewest_steps {
+        circle(point, radius * 2.0, "magenta", "fewest steps");
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -0,0 +1,298 @@
+//! Finds where two wires meet without walking them a step at a time, so
+//! wires billions of steps long are no slower than short ones with as many
+//! turns.
+//!
+//! Sweeping across `x`, horizontal segments are kept ordered by `y` while
+//! they span the sweep position, and each vertical segment looks up the
+//! ones it crosses. Segments lying along the same line are matched up by a
+//! second sweep along that line. Diagonal segments are rare enough to be
+//! checked against every segment of the other wire.
+
+use crate::wire::{Orientation, Segment, Wire};
+use geometry::Point;
+use std::collections::{BTreeMap, HashMap};
+
+/// A run of points, from `from` to `to` inclusive, shared by a segment of
+/// each wire. Wires crossing each other share a single point.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Intersection {
+    pub from: Point,
+    pub to: Point,
+    pub first: Segment,
+    pub second: Segment,
+}
+
+impl Intersection {
+    fn new(first: Segment, second: Segment, from: Point, to: Point) -> Intersection {
+        Intersection {
+            from,
+            to,
+            first,
+            second,
+        }
+    }
+
+    /// The number of points shared.
+    pub fn length(&self) -> u64 {
+        self.from.chebyshev_distance(self.to) as u64 + 1
+    }
+
+    /// Steps along both wires to reach `point`, which must be shared.
+    pub fn steps_to(&self, point: Point) -> u64 {
+        self.first.steps_to(point) + self.second.steps_to(point)
+    }
+
+    /// The lowest and highest positions of the run along the line it's on.
+    pub fn span(&self) -> (i64, i64) {
+        let orientation = self.first.orientation();
+        let from = orientation.position(self.from);
+        let to = orientation.position(self.to);
+        (from.min(to), from.max(to))
+    }
+
+    /// Shared points worth considering when looking for the closest to the
+    /// origin or the fewest steps, leaving out the origin itself. Distance
+    /// from the origin is smallest at an end or where the run crosses an
+    /// axis, and steps along both wires change evenly along the run, so
+    /// the best point is one of these.
+    pub(crate) fn candidates(&self) -> impl Iterator<Item = Point> + '_ {
+        let (low, high) = self.span();
+        // Runs along a diagonal cross the `x` axis at their line.
+        let line = self.first.line();
+        let crosses_x = match self.first.orientation() {
+            Orientation::Diagonal | Orientation::AntiDiagonal => line,
+            Orientation::Horizontal | Orientation::Vertical => 0,
+        };
+
+        IntoIterator::into_iter([
+            low,
+            low + 1,
+            high - 1,
+            high,
+            -1,
+            0,
+            1,
+            crosses_x - 1,
+            crosses_x,
+            crosses_x + 1,
+        ])
+        .map(move |position| self.first.at(position.max(low).min(high)))
+        .filter(|&point| point != Point::ORIGIN)
+    }
+
+    /// The shared point closest to the origin, other than the origin.
+    pub fn closest(&self) -> Option<Point> {
+        self.candidates().min_by_key(|point| point.manhattan())
+    }
+
+    /// The shared point reached in the fewest combined steps, other than
+    /// the origin, along with those steps.
+    pub fn fewest_steps(&self) -> Option<(Point, u64)> {
+        self.candidates()
+            .map(|point| (point, self.steps_to(point)))
+            .min_by_key(|&(_, steps)| steps)
+    }
+}
+
+enum Event {
+    Start(Segment),
+    Query(Segment),
+    End(Segment),
+}
+
+impl Event {
+    fn order(&self) -> (i64, u8) {
+        match self {
+            Event::Start(segment) => (segment.span().0, 0),
+            Event::Query(segment) => (segment.line(), 1),
+            Event::End(segment) => (segment.span().1, 2),
+        }
+    }
+}
+
+/// Crossings between the horizontal segments of one wire and the vertical
+/// segments of the other. `flip` swaps which wire each came from.
+fn crossings(
+    horizontals: &[Segment],
+    verticals: &[Segment],
+    flip: bool,
+    found: &mut Vec<Intersection>,
+) {
+    let mut events = horizontals
+        .iter()
+        .flat_map(|&segment| vec![Event::Start(segment), Event::End(segment)])
+        .chain(verticals.iter().map(|&segment| Event::Query(segment)))
+        .collect::<Vec<Event>>();
+    events.sort_by_key(Event::order);
+
+    let mut active: BTreeMap<i64, Vec<Segment>> = BTreeMap::new();
+    for event in events {
+        match event {
+            Event::Start(segment) => active.entry(segment.line()).or_default().push(segment),
+            Event::End(segment) => {
+                let segments = active.get_mut(&segment.line()).expect("started segment");
+                let index = segments
+                    .iter()
+                    .position(|&active| active == segment)
+                    .expect("started segment");
+                segments.swap_remove(index);
+                if segments.is_empty() {
+                    active.remove(&segment.line());
+                }
+            }
+            Event::Query(vertical) => {
+                let (low, high) = vertical.span();
+                for horizontal in active.range(low..=high).flat_map(|(_, segments)| segments) {
+                    let point = Point::new(vertical.line(), horizontal.line());
+                    let (first, second) = if flip {
+                        (vertical, *horizontal)
+                    } else {
+                        (*horizontal, vertical)
+                    };
+                    found.push(Intersection::new(first, second, point, point));
+                }
+            }
+        }
+    }
+}
+
+/// Overlaps between segments of each wire lying along the same lines.
+fn overlaps(first: &[Segment], second: &[Segment], found: &mut Vec<Intersection>) {
+    let mut lines: HashMap<(Orientation, i64), (Vec<Segment>, Vec<Segment>)> = HashMap::new();
+    for &segment in first {
+        lines
+            .entry((segment.orientation(), segment.line()))
+            .or_default()
+            .0
+            .push(segment);
+    }
+    for &segment in second {
+        lines
+            .entry((segment.orientation(), segment.line()))
+            .or_default()
+            .1
+            .push(segment);
+    }
+
+    for (first, second) in lines.values() {
+        if first.is_empty() || second.is_empty() {
+            continue;
+        }
+
+        let mut starts = first
+            .iter()
+            .map(|&segment| (segment, true))
+            .chain(second.iter().map(|&segment| (segment, false)))
+            .collect::<Vec<(Segment, bool)>>();
+        starts.sort_by_key(|(segment, _)| segment.span().0);
+
+        // Segments of each wire that could still overlap what starts next.
+        let mut active: (Vec<Segment>, Vec<Segment>) = (Vec::new(), Vec::new());
+        for (segment, is_first) in starts {
+            let (low, high) = segment.span();
+            active.0.retain(|other| other.span().1 >= low);
+            active.1.retain(|other| other.span().1 >= low);
+
+            let others = if is_first { &active.1 } else { &active.0 };
+            for &other in others {
+                let end = high.min(other.span().1);
+                let (from, to) = (segment.at(low), segment.at(end));
+                let (first, second) = if is_first {
+                    (segment, other)
+                } else {
+                    (other, segment)
+                };
+                found.push(Intersection::new(first, second, from, to));
+            }
+
+            if is_first {
+                active.0.push(segment);
+            } else {
+                active.1.push(segment);
+            }
+        }
+    }
+}
+
+/// Crossings where at least one of the segments is diagonal.
+fn diagonal_crossings(first: &[Segment], second: &[Segment], found: &mut Vec<Intersection>) {
+    let is_diagonal = |segment: &Segment| match segment.orientation() {
+        Orientation::Diagonal | Orientation::AntiDiagonal => true,
+        Orientation::Horizontal | Orientation::Vertical => false,
+    };
+
+    let second_diagonal = second
+        .iter()
+        .copied()
+        .filter(is_diagonal)
+        .collect::<Vec<Segment>>();
+
+    // A diagonal of the first wire can cross anything in the second, the
+    // rest only the second's diagonals.
+    let pairs = first.iter().flat_map(|first| {
+        let others = if is_diagonal(first) {
+            second
+        } else {
+            &second_diagonal
+        };
+        others.iter().map(move |second| (first, second))
+    });
+    for (first, second) in pairs {
+        if let Some(point) = first.crossing(second) {
+            found.push(Intersection::new(*first, *second, point, point));
+        }
+    }
+}
+
+fn between(first: &[Segment], second: &[Segment]) -> Vec<Intersection> {
+    let of = |segments: &[Segment], orientation: Orientation| {
+        segments
+            .iter()
+            .copied()
+            .filter(|segment| segment.orientation() == orientation)
+            .collect::<Vec<Segment>>()
+    };
+    let first_horizontal = of(first, Orientation::Horizontal);
+    let first_vertical = of(first, Orientation::Vertical);
+    let second_horizontal = of(second, Orientation::Horizontal);
+    let second_vertical = of(second, Orientation::Vertical);
+
+    let mut found = Vec::new();
+    crossings(&first_horizontal, &second_vertical, false, &mut found);
+    crossings(&second_horizontal, &first_vertical, true, &mut found);
+    diagonal_crossings(first, second, &mut found);
+    overlaps(first, second, &mut found);
+    found
+}
+
+/// Every place the two wires share points, including where they both start.
+pub fn intersections(first: &Wire, second: &Wire) -> Vec<Intersection> {
+    between(&first.segments, &second.segments)
+}
+
+/// Every place a wire passes through again, with `first` the segment it
+/// passed through on earlier and `second` the one it came back on. The
+/// corner where one segment turns into the next isn't counted.
+pub fn self_intersections(wire: &Wire) -> Vec<Intersection> {
+    let mut found = between(&wire.segments, &wire.segments);
+    found.retain(|run| run.first.steps < run.second.steps);
+    found.retain_mut(|run| {
+        let corner = run.first.end();
+        if run.second.steps != run.first.steps + run.first.length {
+            return true;
+        }
+
+        // Doubling back over the segment before shares more than the corner.
+        let step = run.second.direction.offset();
+        if run.from == corner && run.to == corner {
+            return false;
+        } else if run.from == corner {
+            run.from += step;
+        } else if run.to == corner {
+            run.to += step;
+        }
+        true
+    });
+    found.sort_by_key(|run| (run.second.steps, run.first.steps));
+    found
+}
@@ -0,0 +1,46 @@
+pub mod diagram;
+pub mod intersection;
+pub mod network;
+pub mod wire;
+
+use common::parse::lines;
+use common::{ParseError, Solution};
+use network::Pair;
+use wire::{parse_wire, Wire};
+
+pub fn parse(input: &str) -> Result<Vec<Wire>, ParseError> {
+    lines(input).map(|line| parse_wire(input, line)).collect()
+}
+
+/// The puzzle only asks about the first two wires.
+pub fn part1(wires: &[Wire]) -> i64 {
+    Pair::new(wires, 0, 1)
+        .closest()
+        .expect("a minimum")
+        .manhattan()
+}
+
+pub fn part2(wires: &[Wire]) -> u64 {
+    let (_, steps) = Pair::new(wires, 0, 1).fewest_steps().expect("a minimum");
+    steps
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Wire>;
+    type Part1 = i64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Wire>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Wire>) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Wire>) -> u64 {
+        part2(input)
+    }
+}
@@ -1,71 +1,100 @@
-use std::collections::{HashMap, HashSet};
-use std::io::{self, BufRead};
+use clap::Parser;
+use day3::diagram::{self, Markers};
+use day3::intersection::self_intersections;
+use day3::network;
+use day3::wire::Visit;
+use day3::Day3;
+use std::fs;
+use std::path::PathBuf;
 
-type Point = (i32, i32);
-const ORIGIN: Point = (0, 0);
+#[derive(Debug, Parser)]
+struct Options {
+    /// List where every pair of wires intersects and where each wire
+    /// crosses itself
+    #[arg(long)]
+    report: bool,
 
-fn part1(wire1: &HashMap<Point, u32>, wire2: &HashMap<Point, u32>) {
-    let wire1 = wire1.keys().cloned().collect::<HashSet<Point>>();
-    let wire2 = wire2.keys().cloned().collect::<HashSet<Point>>();
-    let intersections = wire1.intersection(&wire2);
-    let answer = intersections
-        .filter(|&p| *p != ORIGIN)
-        .map(|(x, y)| x.abs() + y.abs())
-        .min()
-        .expect("a minimum");
-    println!("part 1 = {}", answer);
-}
+    /// Which visit's steps `--report` counts where a wire passes through a
+    /// point more than once: `first` or `latest`. Part 2 always counts the
+    /// first, as the puzzle asks
+    #[arg(long, default_value = "first")]
+    visit: Visit,
+
+    /// List the places where at least this many wires meet
+    #[arg(long, value_name = "WIRES")]
+    at_least: Option<usize>,
+
+    /// Draw the wires and their crossings in the terminal
+    #[arg(long)]
+    draw: bool,
 
-fn part2(wire1: &HashMap<Point, u32>, wire2: &HashMap<Point, u32>) {
-    let keys1 = wire1.keys().cloned().collect::<HashSet<Point>>();
-    let keys2 = wire2.keys().cloned().collect::<HashSet<Point>>();
-    let intersections = keys1.intersection(&keys2);
-    let answer = intersections
-        .filter(|&p| *p != ORIGIN)
-        .map(|position| wire1[position] + wire2[position])
-        .min()
-        .expect("a minimum");
-    println!("part 2 = {}", answer);
+    /// Draw the wires and their crossings to an SVG file
+    #[arg(long, value_name = "PATH")]
+    svg: Option<PathBuf>,
 }
 
 fn main() {
-    let wires = io::stdin()
-        .lock()
-        .lines()
-        .map(|line| line.expect("Failed to read line"))
-        .map(|line| {
-            let mut initial = HashMap::new();
-            initial.insert(ORIGIN, 0);
+    let options = Options::parse();
+    let wires = common::read_input::<Day3>();
+    if wires.len() >= 2 {
+        common::print_answers::<Day3>(&wires);
+    }
 
-            let (acc, _) = line.split(',').map(|path| path.to_string()).fold(
-                (initial, ORIGIN),
-                |(mut acc, previous), path| {
-                    let mut chars = path.chars();
-                    let direction = chars.next().expect("direction");
-                    let length = chars.collect::<String>().parse().expect("length");
-                    let (offset_x, offset_y) = match direction {
-                        'R' => (1, 0),
-                        'L' => (-1, 0),
-                        'U' => (0, 1),
-                        'D' => (0, -1),
-                        _ => unreachable!(),
-                    };
+    if options.report {
+        for pair in network::pairs(&wires) {
+            print!(
+                "wires {} and {}: {} intersection(s)",
+                pair.first + 1,
+                pair.second + 1,
+                pair.intersections.len()
+            );
+            if let Some(point) = pair.closest() {
+                print!(", closest {} at distance {}", point, point.manhattan());
+            }
+            if let Some((point, steps)) = pair.fewest_steps_visiting(&wires, options.visit) {
+                print!(", fewest steps {} after {}", point, steps);
+            }
+            println!();
+        }
 
-                    let steps = acc[&previous];
-                    let mut position = previous;
-                    for i in 1..=length {
-                        let (position_x, position_y) = position;
-                        position = (position_x + offset_x, position_y + offset_y);
-                        acc.insert(position, steps + i);
-                    }
+        for (index, wire) in wires.iter().enumerate() {
+            for run in self_intersections(wire) {
+                print!("wire {} passes {}", index + 1, run.from);
+                if run.from != run.to {
+                    print!(" to {}", run.to);
+                }
+                println!(
+                    " again, after {} and {} steps",
+                    run.first.steps_to(run.from),
+                    run.second.steps_to(run.from)
+                );
+            }
+        }
+    }
 
-                    (acc, position)
-                },
-            );
-            acc
-        })
-        .collect::<Vec<HashMap<Point, u32>>>();
+    if let Some(at_least) = options.at_least {
+        for meeting in network::meetings(&wires, at_least) {
+            let wires = meeting
+                .wires
+                .iter()
+                .map(|wire| (wire + 1).to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            if meeting.from == meeting.to {
+                println!("{}: wires {}", meeting.from, wires);
+            } else {
+                println!("{} to {}: wires {}", meeting.from, meeting.to, wires);
+            }
+        }
+    }
 
-    part1(&wires[0], &wires[1]);
-    part2(&wires[0], &wires[1]);
+    if options.draw || options.svg.is_some() {
+        let markers = Markers::new(&wires);
+        if options.draw {
+            print!("{}", diagram::terminal(&wires, &markers));
+        }
+        if let Some(path) = options.svg {
+            fs::write(path, diagram::svg(&wires, &markers)).expect("could not write SVG");
+        }
+    }
 }
@@ -0,0 +1,298 @@
+//! Intersections between any number of wires: what each pair of wires
+//! shares, and the places where several wires meet.
+
+use crate::intersection::{intersections, Intersection};
+use crate::wire::{Orientation, Segment, Visit, Wire};
+use geometry::Point;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Where two wires, given by their index, intersect.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pair {
+    pub first: usize,
+    pub second: usize,
+    pub intersections: Vec<Intersection>,
+}
+
+impl Pair {
+    pub fn new(wires: &[Wire], first: usize, second: usize) -> Pair {
+        Pair {
+            first,
+            second,
+            intersections: intersections(&wires[first], &wires[second]),
+        }
+    }
+
+    /// The shared point closest to the origin, other than the origin.
+    pub fn closest(&self) -> Option<Point> {
+        self.intersections
+            .iter()
+            .filter_map(Intersection::closest)
+            .min_by_key(|point| point.manhattan())
+    }
+
+    /// The shared point reached in the fewest combined steps, other than
+    /// the origin, with those steps.
+    pub fn fewest_steps(&self) -> Option<(Point, u64)> {
+        self.intersections
+            .iter()
+            .filter_map(Intersection::fewest_steps)
+            .min_by_key(|&(_, steps)| steps)
+    }
+
+    /// Like `fewest_steps`, but counting the visit chosen by `visit` at
+    /// points either wire passes through more than once.
+    pub fn fewest_steps_visiting(&self, wires: &[Wire], visit: Visit) -> Option<(Point, u64)> {
+        if visit == Visit::First {
+            return self.fewest_steps();
+        }
+
+        let (first, second) = (&wires[self.first], &wires[self.second]);
+        self.intersections
+            .iter()
+            .flat_map(|run| run.candidates().chain(revisits(run, &[first, second])))
+            .filter(|&point| point != Point::ORIGIN)
+            .map(|point| {
+                let steps = first.steps_to(point, visit).expect("shared point")
+                    + second.steps_to(point, visit).expect("shared point");
+                (point, steps)
+            })
+            .min_by_key(|&(_, steps)| steps)
+    }
+}
+
+/// Where steps along two segments on the same line would be equal if they
+/// carried on past their ends, when they run in opposite directions.
+fn level(first: &Segment, second: &Segment) -> Option<i64> {
+    let orientation = first.orientation();
+    let slope = |segment: &Segment| {
+        (orientation.position(segment.end()) - orientation.position(segment.start)).signum()
+    };
+    let (first_slope, second_slope) = (slope(first), slope(second));
+    if first_slope == 0 || second_slope == 0 || first_slope == second_slope {
+        return None;
+    }
+
+    let base = |segment: &Segment, slope: i64| {
+        segment.steps as i64 - slope * orientation.position(segment.start)
+    };
+    let difference = base(second, second_slope) - base(first, first_slope);
+    Some((difference * first_slope).div_euclid(2))
+}
+
+/// Points along a shared run where the steps to the latest visit of either
+/// wire may stop changing evenly: around where the wires' other segments
+/// start, end or cross the run, and where two visits along its line are
+/// equally far along. Between these, the best point is at one of the ends.
+fn revisits(run: &Intersection, wires: &[&Wire]) -> Vec<Point> {
+    let (low, high) = run.span();
+    let orientation = run.first.orientation();
+    let line = run.first.line();
+
+    let mut positions = Vec::new();
+    for wire in wires {
+        let along = wire
+            .segments
+            .iter()
+            .filter(|segment| segment.orientation() == orientation && segment.line() == line)
+            .collect::<Vec<&Segment>>();
+        for segment in &along {
+            let (start, end) = segment.span();
+            positions.extend(IntoIterator::into_iter([start - 1, start, start + 1]));
+            positions.extend(IntoIterator::into_iter([end - 1, end, end + 1]));
+        }
+        for segment in &wire.segments {
+            if let Some(point) = segment.crossing(&run.first) {
+                let position = orientation.position(point);
+                positions.extend(IntoIterator::into_iter([
+                    position - 1,
+                    position,
+                    position + 1,
+                ]));
+            }
+        }
+        for (index, first) in along.iter().enumerate() {
+            for second in &along[index + 1..] {
+                if let Some(position) = level(first, second) {
+                    positions.extend(IntoIterator::into_iter([position, position + 1]));
+                }
+            }
+        }
+    }
+
+    positions
+        .into_iter()
+        .filter(|&position| low <= position && position <= high)
+        .map(|position| orientation.at(line, position))
+        .collect()
+}
+
+pub fn pairs(wires: &[Wire]) -> Vec<Pair> {
+    (0..wires.len())
+        .flat_map(|first| (first + 1..wires.len()).map(move |second| (first, second)))
+        .map(|(first, second)| Pair::new(wires, first, second))
+        .collect()
+}
+
+/// A run of points, from `from` to `to` inclusive, that the same wires all
+/// pass through.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Meeting {
+    pub from: Point,
+    pub to: Point,
+    pub wires: Vec<usize>,
+}
+
+fn wires_through(wires: &[Wire], point: Point) -> Vec<usize> {
+    (0..wires.len())
+        .filter(|&wire| wires[wire].contains(point))
+        .collect()
+}
+
+/// The wires running along the whole of a line from `low` to `high`.
+fn wires_along(
+    wires: &[Wire],
+    orientation: Orientation,
+    line: i64,
+    low: i64,
+    high: i64,
+) -> Vec<usize> {
+    (0..wires.len())
+        .filter(|&wire| {
+            wires[wire].segments.iter().any(|segment| {
+                let (start, end) = segment.span();
+                segment.orientation() == orientation
+                    && segment.line() == line
+                    && start <= low
+                    && high <= end
+            })
+        })
+        .collect()
+}
+
+/// Runs of points along one line shared by at least two wires, and the
+/// positions along the line where the wires meeting there may change.
+#[derive(Default)]
+struct SharedLine {
+    runs: Vec<(i64, i64)>,
+    cuts: BTreeSet<i64>,
+}
+
+impl SharedLine {
+    fn contains(&self, low: i64, high: i64) -> bool {
+        self.runs
+            .iter()
+            .any(|&(start, end)| start <= low && high <= end)
+    }
+}
+
+/// Every place other than the origin where at least `at_least` wires meet.
+///
+/// Runs shared along a line are cut wherever a pairwise run starts or ends
+/// and around every point where wires cross, so the wires are the same
+/// along each piece. Neighbouring pieces met by the same wires are joined
+/// back together. A point where runs on different lines meet is left in the
+/// run on the first line only, so no point is in two meetings.
+pub fn meetings(wires: &[Wire], at_least: usize) -> Vec<Meeting> {
+    let shared = pairs(wires)
+        .into_iter()
+        .flat_map(|pair| pair.intersections)
+        .collect::<Vec<Intersection>>();
+
+    // Lines holding a shared run, keyed by their orientation and the
+    // coordinate they're on.
+    let mut lines: HashMap<(Orientation, i64), SharedLine> = HashMap::new();
+    for run in shared.iter().filter(|run| run.length() > 1) {
+        let (low, high) = run.span();
+        let shared = lines
+            .entry((run.first.orientation(), run.first.line()))
+            .or_default();
+        shared.runs.push((low, high));
+        shared.cuts.insert(low);
+        shared.cuts.insert(high + 1);
+    }
+
+    let points = shared
+        .iter()
+        .filter(|run| run.length() == 1)
+        .map(|run| run.from)
+        .chain(IntoIterator::into_iter([Point::ORIGIN]))
+        .collect::<HashSet<Point>>();
+    for &point in &points {
+        for orientation in IntoIterator::into_iter(Orientation::ALL) {
+            if let Some(shared) = lines.get_mut(&(orientation, orientation.line(point))) {
+                let position = orientation.position(point);
+                shared.cuts.insert(position);
+                shared.cuts.insert(position + 1);
+            }
+        }
+    }
+
+    // The first orientation with a shared run through `point`.
+    let owner = |point: Point| {
+        IntoIterator::into_iter(Orientation::ALL).find(|&orientation| {
+            let position = orientation.position(point);
+            lines
+                .get(&(orientation, orientation.line(point)))
+                .is_some_and(|shared| shared.contains(position, position))
+        })
+    };
+
+    let mut found = Vec::new();
+    for (&(orientation, line), shared) in &lines {
+        let at = |position: i64| orientation.at(line, position);
+
+        let cuts = shared.cuts.iter().copied().collect::<Vec<i64>>();
+        let mut current: Option<Meeting> = None;
+        for piece in cuts.windows(2) {
+            let (low, high) = (piece[0], piece[1] - 1);
+            // The origin is cut into a piece of its own, and left out, as are
+            // crossings with runs on lines that come first.
+            let left_out = low == high
+                && (at(low) == Point::ORIGIN
+                    || (points.contains(&at(low)) && owner(at(low)) != Some(orientation)));
+            if !shared.contains(low, high) || left_out {
+                found.extend(current.take());
+                continue;
+            }
+
+            let through = if low == high {
+                wires_through(wires, at(low))
+            } else {
+                wires_along(wires, orientation, line, low, high)
+            };
+
+            match &mut current {
+                Some(meeting) if meeting.wires == through => meeting.to = at(high),
+                _ => {
+                    found.extend(current.take());
+                    current = Some(Meeting {
+                        from: at(low),
+                        to: at(high),
+                        wires: through,
+                    });
+                }
+            }
+        }
+        found.extend(current);
+    }
+
+    found.extend(
+        points
+            .iter()
+            .copied()
+            .filter(|&point| owner(point).is_none())
+            .map(|point| Meeting {
+                from: point,
+                to: point,
+                wires: wires_through(wires, point),
+            }),
+    );
+
+    found.retain(|meeting| {
+        meeting.wires.len() >= at_least.max(2)
+            && !(meeting.from == Point::ORIGIN && meeting.to == Point::ORIGIN)
+    });
+    found.sort_by_key(|meeting| (meeting.from.manhattan(), meeting.from, meeting.to));
+    found
+}
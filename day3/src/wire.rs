@@ -0,0 +1,277 @@
+use common::parse::number;
+use common::ParseError;
+use geometry::Point;
+use std::str::FromStr;
+
+/// The most steps a wire can take. No point on it is further than that from
+/// the origin along either axis, which leaves room to add up coordinates
+/// and steps without overflowing.
+pub const MAX_STEPS: u64 = 1 << 60;
+
+/// Which way a segment of wire runs, including diagonally.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+            Direction::Right => Point::new(1, 0),
+            Direction::UpLeft => Point::new(-1, -1),
+            Direction::UpRight => Point::new(1, -1),
+            Direction::DownLeft => Point::new(-1, 1),
+            Direction::DownRight => Point::new(1, 1),
+        }
+    }
+
+    pub fn orientation(self) -> Orientation {
+        match self {
+            Direction::Left | Direction::Right => Orientation::Horizontal,
+            Direction::Up | Direction::Down => Orientation::Vertical,
+            Direction::UpLeft | Direction::DownRight => Orientation::Diagonal,
+            Direction::UpRight | Direction::DownLeft => Orientation::AntiDiagonal,
+        }
+    }
+}
+
+/// The kinds of line a segment can lie along. Each line is picked out by a
+/// coordinate that stays the same along it, and points on it by a
+/// coordinate that changes: `y` for vertical lines and `x` for the rest.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+    /// Running down and to the right, where `x - y` stays the same.
+    Diagonal,
+    /// Running up and to the right, where `x + y` stays the same.
+    AntiDiagonal,
+}
+
+impl Orientation {
+    pub const ALL: [Orientation; 4] = [
+        Orientation::Horizontal,
+        Orientation::Vertical,
+        Orientation::Diagonal,
+        Orientation::AntiDiagonal,
+    ];
+
+    /// The line through `point`.
+    pub fn line(self, point: Point) -> i64 {
+        let (x, y) = self.coefficients();
+        x * point.x + y * point.y
+    }
+
+    /// Where `point` is along its line.
+    pub fn position(self, point: Point) -> i64 {
+        match self {
+            Orientation::Vertical => point.y,
+            _ => point.x,
+        }
+    }
+
+    /// The point at `position` along `line`.
+    pub fn at(self, line: i64, position: i64) -> Point {
+        match self {
+            Orientation::Horizontal => Point::new(position, line),
+            Orientation::Vertical => Point::new(line, position),
+            Orientation::Diagonal => Point::new(position, position - line),
+            Orientation::AntiDiagonal => Point::new(position, line - position),
+        }
+    }
+
+    /// How much `x` and `y` count towards `line`.
+    fn coefficients(self) -> (i64, i64) {
+        match self {
+            Orientation::Horizontal => (0, 1),
+            Orientation::Vertical => (1, 0),
+            Orientation::Diagonal => (1, -1),
+            Orientation::AntiDiagonal => (1, 1),
+        }
+    }
+}
+
+/// Which visit's steps count at a point a wire passes through more than
+/// once.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Visit {
+    First,
+    Latest,
+}
+
+impl FromStr for Visit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Visit, String> {
+        match s {
+            "first" => Ok(Visit::First),
+            "latest" => Ok(Visit::Latest),
+            _ => Err(format!("expected `first` or `latest`, found `{}`", s)),
+        }
+    }
+}
+
+/// A straight run of a wire, remembering how many steps along the wire it
+/// starts at.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Segment {
+    pub start: Point,
+    pub direction: Direction,
+    pub length: u64,
+    pub steps: u64,
+}
+
+impl Segment {
+    pub fn end(&self) -> Point {
+        self.start + self.direction.offset() * self.length as i64
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.direction.orientation()
+    }
+
+    /// The coordinate that stays the same along the segment.
+    pub fn line(&self) -> i64 {
+        self.orientation().line(self.start)
+    }
+
+    /// The lowest and highest positions along the line the segment covers.
+    pub fn span(&self) -> (i64, i64) {
+        let orientation = self.orientation();
+        let start = orientation.position(self.start);
+        let end = orientation.position(self.end());
+        (start.min(end), start.max(end))
+    }
+
+    /// The point at `position` along the segment's line.
+    pub fn at(&self, position: i64) -> Point {
+        self.orientation().at(self.line(), position)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        let (low, high) = self.span();
+        let orientation = self.orientation();
+        let position = orientation.position(point);
+        orientation.line(point) == self.line() && low <= position && position <= high
+    }
+
+    /// The point where this segment crosses `other`, if they lie along
+    /// different kinds of line and meet at a whole point on both.
+    pub fn crossing(&self, other: &Segment) -> Option<Point> {
+        let (a, b) = self.orientation().coefficients();
+        let (c, d) = other.orientation().coefficients();
+        let determinant = a * d - b * c;
+        if determinant == 0 {
+            return None;
+        }
+
+        let (first, second) = (self.line(), other.line());
+        let x = first * d - second * b;
+        let y = a * second - c * first;
+        if x % determinant != 0 || y % determinant != 0 {
+            return None;
+        }
+
+        let point = Point::new(x / determinant, y / determinant);
+        Some(point).filter(|&point| self.contains(point) && other.contains(point))
+    }
+
+    /// How many steps along the wire it takes to reach `point`, which must
+    /// be on this segment.
+    pub fn steps_to(&self, point: Point) -> u64 {
+        self.steps + self.start.chebyshev_distance(point) as u64
+    }
+}
+
+/// A wire starting at the origin, as a series of segments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wire {
+    pub segments: Vec<Segment>,
+}
+
+impl Wire {
+    pub fn contains(&self, point: Point) -> bool {
+        self.segments.iter().any(|segment| segment.contains(point))
+    }
+
+    /// Total number of steps along the wire.
+    pub fn length(&self) -> u64 {
+        self.segments
+            .last()
+            .map(|segment| segment.steps + segment.length)
+            .unwrap_or(0)
+    }
+
+    /// Steps along the wire to `point`, counting the visit chosen by
+    /// `visit` if it passes through more than once.
+    pub fn steps_to(&self, point: Point, visit: Visit) -> Option<u64> {
+        let steps = self
+            .segments
+            .iter()
+            .filter(|segment| segment.contains(point))
+            .map(|segment| segment.steps_to(point));
+        match visit {
+            Visit::First => steps.min(),
+            Visit::Latest => steps.max(),
+        }
+    }
+}
+
+pub fn parse_wire(input: &str, line: &str) -> Result<Wire, ParseError> {
+    let mut segments = Vec::new();
+    let mut position = Point::ORIGIN;
+    let mut steps = 0u64;
+    for path in line.split(',').map(str::trim) {
+        let letters = path
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(path.len());
+        let direction = match &path[..letters] {
+            "R" => Direction::Right,
+            "L" => Direction::Left,
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            "UL" => Direction::UpLeft,
+            "UR" => Direction::UpRight,
+            "DL" => Direction::DownLeft,
+            "DR" => Direction::DownRight,
+            "" => return Err(ParseError::new(input, path, "expected a direction")),
+            text => {
+                return Err(ParseError::new(
+                    input,
+                    text,
+                    "expected `R`, `L`, `U`, `D`, `UL`, `UR`, `DL` or `DR`",
+                ));
+            }
+        };
+
+        let length: u64 = number(input, &path[letters..])?;
+        let total = steps
+            .checked_add(length)
+            .filter(|&total| total <= MAX_STEPS)
+            .ok_or_else(|| {
+                let message = format!("expected a wire at most {} steps long", MAX_STEPS);
+                ParseError::new(input, path, message)
+            })?;
+        let segment = Segment {
+            start: position,
+            direction,
+            length,
+            steps,
+        };
+        position = segment.end();
+        steps = total;
+        segments.push(segment);
+    }
+
+    Ok(Wire { segments })
+}
@@ -0,0 +1,100 @@
+mod walk;
+
+use day3::diagram::{self, Markers};
+use geometry::Point;
+use walk::wires;
+
+const EXAMPLE: &str = "R8,U5,L5,D3\nU7,R6,D4,L4";
+
+/// The diagram as it reads with the colors taken out.
+fn plain(text: &str) -> String {
+    let mut plain = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+#[test]
+fn marks_the_crossings_of_the_example() {
+    let wires = wires(EXAMPLE);
+    let markers = Markers::new(&wires);
+    assert_eq!(
+        markers,
+        Markers {
+            intersections: vec![Point::new(3, -3), Point::new(6, -5)],
+            closest: Some(Point::new(3, -3)),
+            fewest_steps: Some(Point::new(6, -5)),
+        }
+    );
+}
+
+#[test]
+fn draws_the_example_in_the_terminal() {
+    let wires = wires(EXAMPLE);
+    let text = diagram::terminal(&wires, &Markers::new(&wires));
+    assert_eq!(
+        plain(&text),
+        concat!(
+            "┌─────┐  \n",
+            "│     │  \n",
+            "│  ┌──S─┐\n",
+            "│  │  │ │\n",
+            "│ ─D──┘ │\n",
+            "│  │    │\n",
+            "│       │\n",
+            "o───────┘\n",
+        )
+    );
+}
+
+#[test]
+fn colors_each_wire_and_highlights_the_markers() {
+    let wires = wires(EXAMPLE);
+    let text = diagram::terminal(&wires, &Markers::new(&wires));
+    let rows = text.lines().collect::<Vec<&str>>();
+    assert!(rows[0].starts_with("\x1b[32m┌\x1b[0m"));
+    assert!(rows[7].starts_with("\x1b[1;97mo\x1b[0m\x1b[31m─\x1b[0m"));
+    assert!(rows[4].contains("\x1b[1;97mD\x1b[0m"));
+    assert!(rows[2].contains("\x1b[1;97mS\x1b[0m"));
+}
+
+#[test]
+fn scales_long_wires_down_to_fit() {
+    let wires = wires("R1000,U100\nU50,R1000");
+    assert_eq!(diagram::terminal_scale(&wires), 7);
+    let text = plain(&diagram::terminal(&wires, &Markers::new(&wires)));
+    let mut rows = text.lines();
+    assert_eq!(rows.next(), Some("each character is 7x7"));
+    assert!(rows.all(|row| row.chars().count() <= 160));
+}
+
+#[test]
+fn marks_every_intersection_in_the_svg() {
+    let wires = wires("R8,U5,L5,D3\nU7,R6,D4,L4\nL2,U4,R9");
+    let markers = Markers::new(&wires);
+    assert!(markers.intersections.len() > 2);
+
+    let svg = diagram::svg(&wires, &markers);
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(svg.matches("<polyline").count(), 3);
+    for point in &markers.intersections {
+        let marker = format!(
+            r#"<circle cx="{}" cy="{}" r="0.2" fill="black"><title>intersection {}</title></circle>"#,
+            point.x, point.y, point
+        );
+        assert!(svg.contains(&marker), "no marker for {}", point);
+    }
+    assert_eq!(
+        svg.matches("fill=\"black\"").count(),
+        markers.intersections.len()
+    );
+    assert!(svg.contains(r#"fill="gold"><title>closest (3, -3)</title>"#));
+    assert!(svg.contains(r#"fill="magenta"><title>fewest steps (6, -5)</title>"#));
+}
@@ -0,0 +1,149 @@
+mod walk;
+
+use day3::intersection::intersections;
+use day3::network::Pair;
+use day3::wire::{parse_wire, Direction, MAX_STEPS};
+use geometry::Point;
+use proptest::prelude::*;
+use std::collections::HashSet;
+use walk::{between, points, visits, wire, wire_text, wires};
+
+#[test]
+fn answers_the_puzzle_examples() {
+    let examples = [
+        ("R8,U5,L5,D3\nU7,R6,D4,L4", 6, 30),
+        (
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83",
+            159,
+            610,
+        ),
+        (
+            "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+            135,
+            410,
+        ),
+    ];
+    for &(input, distance, steps) in examples.iter() {
+        let wires = wires(input);
+        assert_eq!(day3::part1(&wires), distance);
+        assert_eq!(day3::part2(&wires), steps);
+    }
+}
+
+#[test]
+fn finds_runs_the_wires_share() {
+    let first = wire("R10");
+    let second = wire("U2,R3,D2,R4,U1");
+    let mut runs = intersections(&first, &second)
+        .into_iter()
+        .map(|run| (run.from.min(run.to), run.from.max(run.to)))
+        .collect::<Vec<(Point, Point)>>();
+    runs.sort();
+    // Where the second wire comes down onto the first and leaves it again,
+    // its vertical segments cross the first as well.
+    assert_eq!(
+        runs,
+        [
+            (Point::new(0, 0), Point::new(0, 0)),
+            (Point::new(3, 0), Point::new(3, 0)),
+            (Point::new(3, 0), Point::new(7, 0)),
+            (Point::new(7, 0), Point::new(7, 0)),
+        ]
+    );
+
+    let pair = Pair::new(&[first, second], 0, 1);
+    assert_eq!(pair.closest(), Some(Point::new(3, 0)));
+    // Three steps along the first wire and seven along the second.
+    assert_eq!(pair.fewest_steps(), Some((Point::new(3, 0), 10)));
+}
+
+#[test]
+fn finds_diagonals_crossing() {
+    let points = |first: &str, second: &str| {
+        let mut points = intersections(&wire(first), &wire(second))
+            .into_iter()
+            .inspect(|run| assert_eq!(run.from, run.to))
+            .map(|run| run.from)
+            .collect::<Vec<Point>>();
+        points.sort();
+        points
+    };
+
+    // A diagonal across a horizontal and a vertical segment.
+    assert_eq!(points("R6", "D3,UR6"), [Point::ORIGIN, Point::new(3, 0)]);
+    assert_eq!(points("D3,UR6", "R6"), [Point::ORIGIN, Point::new(3, 0)]);
+    assert_eq!(points("R2,U5", "UR4"), [Point::ORIGIN, Point::new(2, -2)]);
+    // Diagonals both ways, crossing at a whole point or between them.
+    assert_eq!(points("UR4", "U4,DR4"), [Point::ORIGIN, Point::new(2, -2)]);
+    assert_eq!(points("UR2", "R1,UL1"), [Point::ORIGIN]);
+
+    let wires = wires("R6\nD3,UR6");
+    let pair = Pair::new(&wires, 0, 1);
+    assert_eq!(pair.closest(), Some(Point::new(3, 0)));
+    assert_eq!(pair.fewest_steps(), Some((Point::new(3, 0), 9)));
+}
+
+#[test]
+fn handles_wires_billions_of_steps_long() {
+    let wires = wires("R3000000000,U3000000000,L6000000000\nU1500000000,R5000000000");
+    let pair = Pair::new(&wires, 0, 1);
+    assert_eq!(
+        pair.closest(),
+        Some(Point::new(3_000_000_000, -1_500_000_000))
+    );
+    assert_eq!(
+        pair.fewest_steps(),
+        Some((Point::new(3_000_000_000, -1_500_000_000), 9_000_000_000))
+    );
+    assert_eq!(wires[0].length(), 12_000_000_000);
+    assert_eq!(wires[0].segments[2].direction, Direction::Left);
+}
+
+#[test]
+fn refuses_wires_too_long_to_add_up() {
+    let input = "R1,U1152921504606846975,L18446744073709551615";
+    let error = parse_wire(input, input).unwrap_err();
+    assert_eq!(error.column, 25);
+
+    let input = format!("U{},D1", MAX_STEPS);
+    let error = parse_wire(&input, &input).unwrap_err();
+    assert_eq!(
+        error.message,
+        format!("expected a wire at most {} steps long", MAX_STEPS)
+    );
+    assert!(parse_wire(&input[..input.len() - 3], &input[..input.len() - 3]).is_ok());
+}
+
+proptest! {
+    #[test]
+    fn intersections_match_walking_the_wires(first in wire_text(), second in wire_text()) {
+        let (first, second) = (wire(&first), wire(&second));
+        let shared = points(&first)
+            .intersection(&points(&second))
+            .copied()
+            .collect::<HashSet<Point>>();
+
+        let runs = intersections(&first, &second);
+        let found = runs
+            .iter()
+            .flat_map(|run| between(run.from, run.to))
+            .collect::<HashSet<Point>>();
+        prop_assert_eq!(&found, &shared);
+
+        let (first_visits, second_visits) = (visits(&first), visits(&second));
+        let pair = Pair::new(&[first, second], 0, 1);
+        let closest = shared
+            .iter()
+            .filter(|&&point| point != Point::ORIGIN)
+            .map(|point| point.manhattan())
+            .min();
+        prop_assert_eq!(pair.closest().map(Point::manhattan), closest);
+
+        let fewest = shared
+            .iter()
+            .filter(|&&point| point != Point::ORIGIN)
+            .map(|point| first_visits[point][0] + second_visits[point][0])
+            .min();
+        prop_assert_eq!(pair.fewest_steps().map(|(_, steps)| steps), fewest);
+    }
+}
@@ -0,0 +1,79 @@
+mod walk;
+
+use day3::network::{meetings, pairs, Meeting};
+use geometry::Point;
+use proptest::prelude::*;
+use std::collections::{BTreeSet, HashMap};
+use walk::{between, points, wire_text, wires};
+
+/// The wires through every point other than the origin that at least two
+/// of them pass through.
+fn shared_points(input: &str) -> HashMap<Point, Vec<usize>> {
+    let mut through: HashMap<Point, Vec<usize>> = HashMap::new();
+    for (index, wire) in wires(input).iter().enumerate() {
+        for point in points(wire) {
+            through.entry(point).or_default().push(index);
+        }
+    }
+    through.retain(|&point, wires| point != Point::ORIGIN && wires.len() >= 2);
+    through
+}
+
+#[test]
+fn pairs_up_every_wire() {
+    let wires = wires("R8,U5,L5,D3\nU7,R6,D4,L4\nL2,U4,R9");
+    let pairs = pairs(&wires);
+    let indices = pairs
+        .iter()
+        .map(|pair| (pair.first, pair.second))
+        .collect::<Vec<(usize, usize)>>();
+    assert_eq!(indices, [(0, 1), (0, 2), (1, 2)]);
+    assert_eq!(pairs[0].closest(), Some(Point::new(3, -3)));
+    assert_eq!(pairs[1].closest(), Some(Point::new(3, -4)));
+    assert_eq!(pairs[2].closest(), Some(Point::new(0, -4)));
+}
+
+#[test]
+fn counts_the_corner_between_shared_runs_once() {
+    let input = "R5,U5\nR5,U5,R1\nU1,R7";
+    let found = meetings(&wires(input), 2);
+    assert_eq!(
+        found,
+        [
+            Meeting {
+                from: Point::new(1, 0),
+                to: Point::new(5, 0),
+                wires: vec![0, 1],
+            },
+            Meeting {
+                from: Point::new(5, -1),
+                to: Point::new(5, -1),
+                wires: vec![0, 1, 2],
+            },
+            Meeting {
+                from: Point::new(5, -5),
+                to: Point::new(5, -2),
+                wires: vec![0, 1],
+            },
+        ]
+    );
+    assert_eq!(meetings(&wires(input), 3).len(), 1);
+}
+
+proptest! {
+    #[test]
+    fn meetings_cover_each_shared_point_once(
+        texts in prop::collection::vec(wire_text(), 3..5),
+    ) {
+        let input = texts.join("\n");
+        let mut expected = shared_points(&input);
+
+        for meeting in meetings(&wires(&input), 2) {
+            for point in between(meeting.from, meeting.to) {
+                let through = expected.remove(&point);
+                prop_assert_eq!(through.as_ref(), Some(&meeting.wires), "at {}", point);
+            }
+        }
+        prop_assert!(expected.is_empty(), "missed {:?}", expected.keys().collect::<BTreeSet<_>>());
+    }
+}
@@ -0,0 +1,131 @@
+mod walk;
+
+use day3::intersection::self_intersections;
+use day3::network::Pair;
+use day3::wire::Visit;
+use geometry::Point;
+use proptest::prelude::*;
+use std::collections::HashSet;
+use walk::{between, points, visits, wire, wire_text, wires};
+
+fn runs(text: &str) -> Vec<(Point, Point, u64, u64)> {
+    self_intersections(&wire(text))
+        .into_iter()
+        .map(|run| (run.from, run.to, run.first.steps, run.second.steps))
+        .collect()
+}
+
+#[test]
+fn leaves_out_the_corners_of_a_wire_turning() {
+    assert_eq!(runs("R3,U3,L3"), []);
+    assert_eq!(runs("R2,R3,UR1"), []);
+}
+
+#[test]
+fn finds_a_wire_crossing_itself() {
+    assert_eq!(
+        runs("R4,U2,L2,D4"),
+        [(Point::new(2, 0), Point::new(2, 0), 0, 8)]
+    );
+    // Diagonals crossing between whole points share none.
+    assert_eq!(runs("R1,UR1,L1,DR1"), []);
+    assert_eq!(
+        runs("UR2,D2,UL2"),
+        [(Point::new(1, -1), Point::new(1, -1), 0, 4)]
+    );
+    // Coming back to where it started.
+    assert_eq!(runs("UR2,D2,L2"), [(Point::ORIGIN, Point::ORIGIN, 0, 4)]);
+}
+
+#[test]
+fn trims_the_corner_off_a_wire_doubling_back() {
+    // Back over all but the corner of the segment before.
+    assert_eq!(runs("R5,L3"), [(Point::new(2, 0), Point::new(4, 0), 0, 5)]);
+    // Back past where the segment before started, onto the origin.
+    assert_eq!(runs("U2,D4"), [(Point::new(0, -1), Point::ORIGIN, 0, 2)]);
+    // Back a single step, which only shares the corner.
+    assert_eq!(runs("R1,L1"), [(Point::ORIGIN, Point::ORIGIN, 0, 1)]);
+}
+
+#[test]
+fn counts_the_visit_asked_for() {
+    // The first wire crosses itself at (2, 0), two steps in and again ten
+    // steps in, where the second wire ends.
+    let wires = wires("R4,U2,L2,D4\nU1,R2,D1");
+    assert_eq!(wires[0].steps_to(Point::new(2, 0), Visit::First), Some(2));
+    assert_eq!(wires[0].steps_to(Point::new(2, 0), Visit::Latest), Some(10));
+
+    let pair = Pair::new(&wires, 0, 1);
+    assert_eq!(
+        pair.fewest_steps_visiting(&wires, Visit::First),
+        Some((Point::new(2, 0), 6))
+    );
+    assert_eq!(
+        pair.fewest_steps_visiting(&wires, Visit::First),
+        pair.fewest_steps()
+    );
+    // Counting the latest visit, the point the first wire reaches on its
+    // way back down is better.
+    assert_eq!(
+        pair.fewest_steps_visiting(&wires, Visit::Latest),
+        Some((Point::new(2, -1), 12))
+    );
+}
+
+proptest! {
+    #[test]
+    fn self_intersections_match_walking_the_wire(text in wire_text()) {
+        let wire = wire(&text);
+        let visits = visits(&wire);
+        let again = visits
+            .iter()
+            .filter(|(_, steps)| steps.len() > 1)
+            .map(|(&point, _)| point)
+            .collect::<HashSet<Point>>();
+
+        let mut found = HashSet::new();
+        for run in self_intersections(&wire) {
+            prop_assert!(run.first.steps < run.second.steps);
+            for point in between(run.from, run.to) {
+                let steps = &visits[&point];
+                let (first, second) = (run.first.steps_to(point), run.second.steps_to(point));
+                prop_assert!(first < second);
+                prop_assert!(steps.contains(&first) && steps.contains(&second));
+                found.insert(point);
+            }
+        }
+        prop_assert_eq!(found, again);
+    }
+
+    #[test]
+    fn visits_count_the_steps_asked_for(first in wire_text(), second in wire_text()) {
+        let wires = wires(&format!("{}\n{}", first, second));
+        let (first, second) = (visits(&wires[0]), visits(&wires[1]));
+        let shared = points(&wires[0])
+            .intersection(&points(&wires[1]))
+            .copied()
+            .filter(|&point| point != Point::ORIGIN)
+            .collect::<Vec<Point>>();
+        let fewest = |pick: fn(&Vec<u64>) -> u64| {
+            shared
+                .iter()
+                .map(|point| pick(&first[point]) + pick(&second[point]))
+                .min()
+        };
+
+        let pair = Pair::new(&wires, 0, 1);
+        for (visit, pick) in [
+            (Visit::First, (|steps: &Vec<u64>| steps[0]) as fn(&Vec<u64>) -> u64),
+            (Visit::Latest, |steps: &Vec<u64>| steps[steps.len() - 1]),
+        ] {
+            let found = pair.fewest_steps_visiting(&wires, visit);
+            prop_assert_eq!(found.map(|(_, steps)| steps), fewest(pick));
+            if let Some((point, steps)) = found {
+                prop_assert!(shared.contains(&point));
+                let counted = wires[0].steps_to(point, visit).unwrap()
+                    + wires[1].steps_to(point, visit).unwrap();
+                prop_assert_eq!(counted, steps);
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Walks wires a step at a time, to check the answers worked out from
+//! whole segments against. Each test file uses only some of these.
+#![allow(dead_code)]
+
+use day3::wire::{parse_wire, Wire};
+use geometry::Point;
+use proptest::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+pub fn wire(text: &str) -> Wire {
+    parse_wire(text, text).unwrap()
+}
+
+pub fn wires(input: &str) -> Vec<Wire> {
+    day3::parse(input).unwrap()
+}
+
+/// Every point the wire visits, with the steps to each visit in order.
+pub fn visits(wire: &Wire) -> HashMap<Point, Vec<u64>> {
+    let mut visits: HashMap<Point, Vec<u64>> = HashMap::new();
+    visits.entry(Point::ORIGIN).or_default().push(0);
+    let mut point = Point::ORIGIN;
+    let mut steps = 0;
+    for segment in &wire.segments {
+        for _ in 0..segment.length {
+            point += segment.direction.offset();
+            steps += 1;
+            visits.entry(point).or_default().push(steps);
+        }
+    }
+    visits
+}
+
+pub fn points(wire: &Wire) -> HashSet<Point> {
+    visits(wire).into_keys().collect()
+}
+
+/// Every point from `from` to `to` inclusive, which must lie along a
+/// horizontal, vertical or diagonal line.
+pub fn between(from: Point, to: Point) -> Vec<Point> {
+    let step = Point::new((to.x - from.x).signum(), (to.y - from.y).signum());
+    let mut points = vec![from];
+    let mut point = from;
+    while point != to {
+        point += step;
+        points.push(point);
+    }
+    points
+}
+
+/// Short wires in all eight directions, written as the puzzle writes them.
+pub fn wire_text() -> impl Strategy<Value = String> {
+    let direction = prop::sample::select(vec!["R", "L", "U", "D", "UL", "UR", "DL", "DR"]);
+    prop::collection::vec((direction, 1u64..6), 1..8).prop_map(|paths| {
+        paths
+            .iter()
+            .map(|(direction, length)| format!("{}{}", direction, length))
+            .collect::<Vec<String>>()
+            .join(",")
+    })
+}
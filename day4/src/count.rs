@@ -0,0 +1,276 @@
+//! Counts the codes in a range without looking at each one, by building
+//! them a digit at a time and counting every code sharing a prefix at once.
+//!
+//! Once a prefix is below the same prefix of the range's end, the rest of
+//! the code can be anything, so how many ways there are to finish it only
+//! depends on how many digits are left and a little state the rules need:
+//! the last digit, the run it's part of, the sum so far and which rules an
+//! earlier run already met.
+
+use crate::rules::{digits, Rule, Rules, MAX_RULES};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// Where a code is up to, after its last digit.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct State {
+    last: Option<u32>,
+    /// How long the run the last digit is in has been, up to one more than
+    /// any of the rules care about.
+    run: u32,
+    /// The sum of the digits, if any of the rules care about it.
+    sum: u32,
+    /// Which rules, by index, an earlier run already met.
+    met: u64,
+}
+
+impl State {
+    pub(crate) const EMPTY: State = State {
+        last: None,
+        run: 0,
+        sum: 0,
+        met: 0,
+    };
+
+    fn meets(rule: &Rule, run: u32) -> bool {
+        match *rule {
+            Rule::HasRun { min } => run >= min,
+            Rule::HasExactRun(length) => run == length,
+            _ => false,
+        }
+    }
+
+    /// The state after `digit`, or `None` if no code can carry on with it.
+    pub(crate) fn push(self, digit: u32, rules: &Rules) -> Option<State> {
+        let mut run_cap = 1;
+        let mut sum_cap = 0;
+        for rule in &rules.rules {
+            match *rule {
+                Rule::NonDecreasing if self.last.is_some_and(|last| digit < last) => return None,
+                Rule::ForbidDigit(forbidden) if digit == forbidden => return None,
+                Rule::DigitSum { high, .. } if self.sum + digit > high => return None,
+                Rule::HasRun { min } => run_cap = run_cap.max(min),
+                Rule::HasExactRun(length) => run_cap = run_cap.max(length + 1),
+                Rule::DigitSum { high, .. } => sum_cap = sum_cap.max(high),
+                _ => {}
+            }
+        }
+
+        let mut next = State {
+            last: Some(digit),
+            run: 1,
+            sum: (self.sum + digit).min(sum_cap),
+            met: self.met,
+        };
+        if self.last == Some(digit) {
+            next.run = (self.run + 1).min(run_cap);
+        } else {
+            next.met |= self.met_now(rules);
+        }
+        Some(next)
+    }
+
+    /// The rules met by the run the last digit is in.
+    fn met_now(self, rules: &Rules) -> u64 {
+        rules
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| State::meets(rule, self.run))
+            .fold(0, |met, (index, _)| met | 1 << index)
+    }
+
+    /// Whether a code can end here.
+    pub(crate) fn finished(self, rules: &Rules) -> bool {
+        let met = self.met | self.met_now(rules);
+        self.last.is_some()
+            && rules
+                .rules
+                .iter()
+                .enumerate()
+                .all(|(index, rule)| match *rule {
+                    Rule::HasRun { .. } | Rule::HasExactRun(_) => met & 1 << index != 0,
+                    Rule::DigitSum { low, .. } => self.sum >= low,
+                    _ => true,
+                })
+    }
+}
+
+/// Counts the ways to finish codes, remembering the answers so counts for
+/// the same number of digits left and the same state are only worked out
+/// once. Counts are `u128`, as every `u64` can be a code following the
+/// rules and there's one more of them than a `u64` holds.
+pub(crate) struct Counter<'a> {
+    pub(crate) rules: &'a Rules,
+    completions: HashMap<(u32, State), u128>,
+}
+
+impl<'a> Counter<'a> {
+    pub(crate) fn new(rules: &'a Rules) -> Counter<'a> {
+        assert!(rules.base >= 2, "a base of at least 2");
+        assert!(rules.rules.len() <= MAX_RULES, "at most 64 rules");
+        Counter {
+            rules,
+            completions: HashMap::new(),
+        }
+    }
+
+    /// The ways to finish a code from `state` with `left` more digits.
+    pub(crate) fn complete(&mut self, left: u32, state: State) -> u128 {
+        if left == 0 {
+            return state.finished(self.rules) as u128;
+        }
+        if let Some(&count) = self.completions.get(&(left, state)) {
+            return count;
+        }
+
+        let rules = self.rules;
+        let count = (0..rules.base)
+            .filter_map(|digit| state.push(digit, rules))
+            .map(|next| self.complete(left - 1, next))
+            .sum();
+        self.completions.insert((left, state), count);
+        count
+    }
+
+    /// The lowest digit a code with `length` digits can start with. Only
+    /// zero itself starts with a zero.
+    pub(crate) fn lowest_first(length: u32) -> u32 {
+        if length == 1 {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Codes with exactly `length` digits.
+    fn with_length(&mut self, length: u32) -> u128 {
+        let rules = self.rules;
+        (Counter::lowest_first(length)..rules.base)
+            .filter_map(|digit| State::EMPTY.push(digit, rules))
+            .map(|state| self.complete(length - 1, state))
+            .sum()
+    }
+
+    /// Codes from zero up to and including `end`.
+    pub(crate) fn up_to(&mut self, end: u64) -> u128 {
+        let rules = self.rules;
+        let digits = digits(end, rules.base);
+        let length = digits.len() as u32;
+        let mut count = (1..length)
+            .map(|length| self.with_length(length))
+            .sum::<u128>();
+
+        let mut state = State::EMPTY;
+        for (index, &digit) in digits.iter().enumerate() {
+            let left = length - index as u32 - 1;
+            let lowest = if index == 0 {
+                Counter::lowest_first(length)
+            } else {
+                0
+            };
+            count += (lowest..digit)
+                .filter_map(|below| state.push(below, rules))
+                .map(|next| self.complete(left, next))
+                .sum::<u128>();
+            match state.push(digit, rules) {
+                Some(next) => state = next,
+                None => return count,
+            }
+        }
+        count + state.finished(rules) as u128
+    }
+}
+
+impl<'a> Counter<'a> {
+    /// The code with `rank` codes following the rules below it.
+    pub(crate) fn nth(&mut self, mut rank: u128) -> Option<u64> {
+        let rules = self.rules;
+        let base = u64::from(rules.base);
+        let mut length = 1;
+        loop {
+            // Codes with more digits than a `u64` holds can't be reached,
+            // and those with the most digits it holds only up to its end.
+            let codes = with_length(rules.base, length)?;
+            let count = if *codes.end() == u64::MAX {
+                self.up_to(u64::MAX) - self.up_to(codes.start() - 1)
+            } else {
+                self.with_length(length)
+            };
+            if rank < count {
+                break;
+            }
+            rank -= count;
+            length += 1;
+        }
+
+        let mut state = State::EMPTY;
+        let mut value = 0;
+        for index in 0..length {
+            let left = length - index - 1;
+            let lowest = if index == 0 {
+                Counter::lowest_first(length)
+            } else {
+                0
+            };
+            for digit in lowest..rules.base {
+                let next = match state.push(digit, rules) {
+                    Some(next) => next,
+                    None => continue,
+                };
+                let count = self.complete(left, next);
+                if rank < count {
+                    state = next;
+                    value = value * base + u64::from(digit);
+                    break;
+                }
+                rank -= count;
+            }
+        }
+        Some(value)
+    }
+}
+
+/// The range of codes with `length` digits, if any fit in a `u64`.
+fn with_length(base: u32, length: u32) -> Option<RangeInclusive<u64>> {
+    let base = u64::from(base);
+    let low = match length {
+        0 => return None,
+        1 => 0,
+        _ => base.checked_pow(length - 1)?,
+    };
+    let high = base
+        .checked_pow(length)
+        .map(|high| high - 1)
+        .unwrap_or(u64::MAX);
+    Some(low..=high)
+}
+
+/// Narrows `range` to the codes with as many digits as the rules ask for,
+/// or `None` if there aren't any.
+pub(crate) fn narrow(range: RangeInclusive<u64>, rules: &Rules) -> Option<RangeInclusive<u64>> {
+    let (mut start, mut end) = range.into_inner();
+    for rule in &rules.rules {
+        if let Rule::Digits(length) = *rule {
+            let codes = with_length(rules.base, length)?;
+            start = start.max(*codes.start());
+            end = end.min(*codes.end());
+        }
+    }
+    Some(start..=end).filter(|range| !range.is_empty())
+}
+
+/// How many codes in `range` follow `rules`.
+pub fn count(range: RangeInclusive<u64>, rules: &Rules) -> u128 {
+    let (start, end) = match narrow(range, rules) {
+        Some(range) => range.into_inner(),
+        None => return 0,
+    };
+
+    let mut counter = Counter::new(rules);
+    let below = match start {
+        0 => 0,
+        start => counter.up_to(start - 1),
+    };
+    counter.up_to(end) - below
+}
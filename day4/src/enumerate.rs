@@ -0,0 +1,117 @@
+//! Lists the codes in a range that follow the rules, in order, building
+//! them a digit at a time and skipping any prefix the counter says can't
+//! be finished.
+
+use crate::count::{narrow, Counter, State};
+use crate::rules::{digits, Rules};
+use std::ops::RangeInclusive;
+
+/// A prefix of the codes being listed and the next digit to try after it.
+struct Prefix {
+    value: u64,
+    length: u32,
+    state: State,
+    next: u32,
+}
+
+pub struct Codes<'a> {
+    counter: Counter<'a>,
+    start: u64,
+    end: u64,
+    /// How many digits the codes being listed have, and the most any will.
+    length: u32,
+    longest: u32,
+    stack: Vec<Prefix>,
+}
+
+impl<'a> Codes<'a> {
+    fn root(&self) -> Prefix {
+        Prefix {
+            value: 0,
+            length: 0,
+            state: State::EMPTY,
+            next: Counter::lowest_first(self.length),
+        }
+    }
+}
+
+impl<'a> Iterator for Codes<'a> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let base = self.counter.rules.base;
+        loop {
+            let prefix = match self.stack.last_mut() {
+                Some(prefix) => prefix,
+                None if self.length < self.longest => {
+                    self.length += 1;
+                    let root = self.root();
+                    self.stack.push(root);
+                    continue;
+                }
+                None => return None,
+            };
+            if prefix.next == base {
+                self.stack.pop();
+                continue;
+            }
+
+            let digit = prefix.next;
+            prefix.next += 1;
+            let (value, length) = (prefix.value, prefix.length + 1);
+            let state = match prefix.state.push(digit, self.counter.rules) {
+                Some(state) => state,
+                None => continue,
+            };
+
+            // Every code starting with this prefix, which may not fit.
+            let left = self.length - length;
+            let scale = u128::from(base).pow(left);
+            let value = value * u64::from(base) + u64::from(digit);
+            let low = u128::from(value) * scale;
+            let high = low + scale - 1;
+            if high < u128::from(self.start) {
+                continue;
+            }
+            if low > u128::from(self.end) {
+                self.stack.pop();
+                continue;
+            }
+
+            if left == 0 {
+                if state.finished(self.counter.rules) {
+                    return Some(value);
+                }
+            } else if self.counter.complete(left, state) > 0 {
+                self.stack.push(Prefix {
+                    value,
+                    length,
+                    state,
+                    next: 0,
+                });
+            }
+        }
+    }
+}
+
+/// The codes in `range` that follow `rules`, from lowest to highest.
+pub fn codes(range: RangeInclusive<u64>, rules: &Rules) -> Codes<'_> {
+    let (start, end, length, longest) = match narrow(range, rules) {
+        Some(range) => {
+            let (start, end) = range.into_inner();
+            let shortest = digits(start, rules.base).len() as u32;
+            let longest = digits(end, rules.base).len() as u32;
+            (start, end, shortest - 1, longest)
+        }
+        None => (1, 0, 0, 0),
+    };
+
+    Codes {
+        counter: Counter::new(rules),
+        start,
+        end,
+        length,
+        longest,
+        stack: Vec::new(),
+    }
+}
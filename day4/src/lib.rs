@@ -0,0 +1,54 @@
+pub mod count;
+pub mod enumerate;
+pub mod rules;
+pub mod sample;
+
+use common::parse::split_once;
+use common::{ParseError, Solution};
+use rules::Rules;
+use std::ops::RangeInclusive;
+
+fn code(input: &str, text: &str, base: u32) -> Result<u64, ParseError> {
+    u64::from_str_radix(text, base).map_err(|_| {
+        let message = format!("expected a code in base {}", base);
+        ParseError::new(input, text, message)
+    })
+}
+
+/// Parses a range of codes written in `base` as `low-high`.
+pub fn parse_range(input: &str, base: u32) -> Result<RangeInclusive<u64>, ParseError> {
+    let (low, high) = split_once(input, input.trim(), '-')?;
+    Ok(code(input, low.trim(), base)?..=code(input, high.trim(), base)?)
+}
+
+pub fn parse(input: &str) -> Result<RangeInclusive<u64>, ParseError> {
+    parse_range(input, 10)
+}
+
+pub fn part1(range: &RangeInclusive<u64>) -> u128 {
+    count::count(range.clone(), &Rules::part1())
+}
+
+pub fn part2(range: &RangeInclusive<u64>) -> u128 {
+    count::count(range.clone(), &Rules::part2())
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = RangeInclusive<u64>;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> Result<RangeInclusive<u64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &RangeInclusive<u64>) -> u128 {
+        part1(input)
+    }
+
+    fn part2(input: &RangeInclusive<u64>) -> u128 {
+        part2(input)
+    }
+}
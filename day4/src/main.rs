@@ -1,52 +1,181 @@
-const MIN: u32 = 108_457;
-const MAX: u32 = 562_041;
-
-fn is_sorted(code: &str) -> bool {
-    let mut sorted = code.as_bytes().to_vec();
-    sorted.sort();
-    sorted == code.as_bytes()
-}
-
-fn part1() {
-    let answer = (MIN..=MAX)
-        .map(|code| code.to_string())
-        .filter(|code| code.as_bytes().windows(2).any(|part| part[0] == part[1]))
-        .filter(|code| is_sorted(&code))
-        .count();
-    println!("part 1 = {}", answer);
-}
-
-fn part2() {
-    let answer = (MIN..=MAX)
-        .map(|code| code.to_string())
-        .filter(|code| {
-            let bytes = code.as_bytes();
-            for i in 0..5 {
-                let a = bytes[i];
-                let b = bytes[i + 1];
-                if a == b {
-                    let neighbor_match = [-1i8, 2]
-                        .iter()
-                        .map(|&n| i as i8 + n)
-                        .filter(|&n| n >= 0 && n < 6)
-                        .map(|n| bytes[n as usize])
-                        .any(|c| c == a);
-                    if neighbor_match {
-                        continue;
-                    } else {
-                        return true;
-                    }
-                }
+use clap::{Parser, Subcommand};
+use day4::rules::{self, format_code, Rules};
+use day4::sample::{self, Rng};
+use day4::{count, enumerate, Day4};
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Parser)]
+struct Options {
+    /// The codes to consider, written `low-high`, instead of reading them
+    /// from stdin
+    range: Option<String>,
+
+    /// Count the codes following these rules instead of answering the
+    /// puzzle, such as `digits(6), non_decreasing, has_exact_run(2)`
+    #[arg(long, global = true, conflicts_with = "rules_file")]
+    rules: Option<String>,
+
+    /// Read the rules to count codes with from a file
+    #[arg(long, global = true, value_name = "PATH")]
+    rules_file: Option<PathBuf>,
+
+    /// The base codes are written in, including the range
+    #[arg(
+        long,
+        global = true,
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u32).range(2..=36)
+    )]
+    base: u32,
+
+    /// List every code following the rules, or part 1's rules if there
+    /// aren't any
+    #[arg(long)]
+    list: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Show which rules each code passes or fails and why, using both
+    /// parts' rules if there aren't any, and whether it's in the range if
+    /// one is given
+    Explain {
+        #[arg(required = true)]
+        codes: Vec<String>,
+    },
+
+    /// Pick different codes in the range following the rules at random,
+    /// each as likely as any other, using part 2's rules if there aren't
+    /// any
+    Generate {
+        /// How many codes to pick
+        #[arg(long, default_value_t = 10)]
+        count: u64,
+
+        /// Pick the same codes as another run with this seed
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+}
+
+fn exit_with(what: &str, error: impl std::fmt::Display) -> ! {
+    eprintln!("Failed to parse {}: {}", what, error);
+    process::exit(1);
+}
+
+fn range(options: &Options) -> RangeInclusive<u64> {
+    match &options.range {
+        Some(range) => {
+            day4::parse_range(range, options.base).unwrap_or_else(|e| exit_with("range", e))
+        }
+        None if options.base == 10 => common::read_input::<Day4>(),
+        None => exit_with("range", "expected one on the command line with `--base`"),
+    }
+}
+
+/// The rules given on the command line, if there are any.
+fn rules(options: &Options) -> Option<Rules> {
+    let text = match (&options.rules, &options.rules_file) {
+        (Some(text), _) => text.clone(),
+        (None, Some(path)) => fs::read_to_string(path).expect("Failed to read rules"),
+        (None, None) => return None,
+    };
+    Some(rules::parse(&text, options.base).unwrap_or_else(|e| exit_with("rules", e)))
+}
+
+fn explain(options: &Options, codes: &[String]) {
+    let rules = rules(options).unwrap_or_else(|| {
+        let mut rules = Rules::part1();
+        for rule in Rules::part2().rules {
+            if !rules.rules.contains(&rule) {
+                rules.rules.push(rule);
             }
+        }
+        rules
+    });
+    let range = options.range.as_ref().map(|_| range(options));
 
-            false
-        })
-        .filter(|code| is_sorted(&code))
-        .count();
-    println!("part 2 = {}", answer);
+    for text in codes {
+        let code = u64::from_str_radix(text, rules.base).unwrap_or_else(|_| {
+            let message = format!("expected a code in base {}, found `{}`", rules.base, text);
+            exit_with("code", message)
+        });
+        let checks = rules.explain(code);
+        let in_range = range.as_ref().map(|range| range.contains(&code));
+        let passed = checks.iter().all(|check| check.passed) && in_range != Some(false);
+
+        println!("{}: {}", text, if passed { "passes" } else { "fails" });
+        for check in checks {
+            let mark = if check.passed { "pass" } else { "fail" };
+            println!("  {} {}: {}", mark, check.rule, check.reason);
+        }
+        if let (Some(range), Some(inside)) = (&range, in_range) {
+            let (start, end) = (range.start(), range.end());
+            let reason = if inside {
+                "inside"
+            } else if code < *start {
+                "below"
+            } else {
+                "above"
+            };
+            println!(
+                "  {} range {}-{}: {}",
+                if inside { "pass" } else { "fail" },
+                format_code(*start, rules.base),
+                format_code(*end, rules.base),
+                reason
+            );
+        }
+    }
+}
+
+fn generate(options: &Options, count: u64, seed: Option<u64>) {
+    let range = range(options);
+    let rules = rules(options).unwrap_or_else(Rules::part2);
+    let seed = seed.unwrap_or_else(|| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("after 1970");
+        let seed = now.as_nanos() as u64;
+        eprintln!("seed = {}", seed);
+        seed
+    });
+
+    for code in sample::sample(range, &rules, count, &mut Rng::new(seed)) {
+        println!("{}", format_code(code, rules.base));
+    }
 }
 
 fn main() {
-    part1();
-    part2();
+    let options = Options::parse();
+    match &options.command {
+        Some(Command::Explain { codes }) => return explain(&options, codes),
+        Some(Command::Generate { count, seed }) => return generate(&options, *count, *seed),
+        None => {}
+    }
+
+    let range = range(&options);
+    let rules = match rules(&options) {
+        Some(rules) => {
+            println!("count = {}", count::count(range.clone(), &rules));
+            rules
+        }
+        None => {
+            common::print_answers::<Day4>(&range);
+            Rules::part1()
+        }
+    };
+
+    if options.list {
+        for code in enumerate::codes(range, &rules) {
+            println!("{}", format_code(code, rules.base));
+        }
+    }
 }
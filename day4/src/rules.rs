@@ -0,0 +1,359 @@
+//! A small language for the rules a code has to follow, written one rule
+//! after another separated by commas or new lines:
+//!
+//! ```text
+//! digits(6), non_decreasing
+//! has_exact_run(2)   # a pair that isn't part of a longer run
+//! digit_sum(in 20..30), forbid_digit(7)
+//! ```
+
+use common::parse::number;
+use common::ParseError;
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Rule {
+    /// Exactly this many digits.
+    Digits(u32),
+    /// Digits never decrease from left to right.
+    NonDecreasing,
+    /// A run of at least `min` adjacent digits the same.
+    HasRun {
+        min: u32,
+    },
+    /// A run of exactly this many of the same digit, not part of a longer
+    /// run.
+    HasExactRun(u32),
+    /// Digits adding up to between `low` and `high` inclusive.
+    DigitSum {
+        low: u32,
+        high: u32,
+    },
+    ForbidDigit(u32),
+}
+
+/// The runs of the same digit in `digits`, as the digit and how many times
+/// it repeats.
+fn runs(digits: &[u32]) -> Vec<(u32, u32)> {
+    let mut runs: Vec<(u32, u32)> = Vec::new();
+    for &digit in digits {
+        match runs.last_mut() {
+            Some((last, length)) if *last == digit => *length += 1,
+            _ => runs.push((digit, 1)),
+        }
+    }
+    runs
+}
+
+fn digit_char(digit: u32, base: u32) -> char {
+    std::char::from_digit(digit, base).expect("a base of at most 36")
+}
+
+/// Whether a code passed one of the rules, and why.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Check {
+    pub rule: Rule,
+    pub passed: bool,
+    pub reason: String,
+}
+
+impl Rule {
+    /// Checks the rule against a code's digits.
+    pub fn check(&self, digits: &[u32]) -> bool {
+        match *self {
+            Rule::Digits(length) => digits.len() == length as usize,
+            Rule::NonDecreasing => digits.windows(2).all(|pair| pair[0] <= pair[1]),
+            Rule::HasRun { min } => runs(digits).into_iter().any(|(_, run)| run >= min),
+            Rule::HasExactRun(length) => runs(digits).into_iter().any(|(_, run)| run == length),
+            Rule::DigitSum { low, high } => {
+                let sum = digits.iter().sum::<u32>();
+                low <= sum && sum <= high
+            }
+            Rule::ForbidDigit(forbidden) => !digits.contains(&forbidden),
+        }
+    }
+
+    /// Checks the rule against a code's digits in `base`, saying why it
+    /// passed or failed.
+    pub fn explain(&self, digits: &[u32], base: u32) -> Check {
+        let runs = runs(digits);
+        let run_text = |&(digit, length): &(u32, u32)| {
+            std::iter::repeat_n(digit_char(digit, base), length as usize).collect::<String>()
+        };
+        let reason = match *self {
+            Rule::Digits(length) if digits.len() == length as usize => {
+                format!("has {} digits", length)
+            }
+            Rule::Digits(length) => format!("has {} digits, not {}", digits.len(), length),
+            Rule::NonDecreasing => match digits.windows(2).position(|pair| pair[0] > pair[1]) {
+                Some(index) => format!(
+                    "{} comes after {} at digit {}",
+                    digit_char(digits[index + 1], base),
+                    digit_char(digits[index], base),
+                    index + 2
+                ),
+                None => "digits never decrease".to_string(),
+            },
+            Rule::HasRun { min } => match runs.iter().find(|&&(_, length)| length >= min) {
+                Some(run) => format!("{} is a run of {}", run_text(run), run.1),
+                None => {
+                    let longest = runs.iter().map(|&(_, length)| length).max();
+                    format!("the longest run is {}", longest.unwrap_or(0))
+                }
+            },
+            Rule::HasExactRun(length) => match runs.iter().find(|&&(_, run)| run == length) {
+                Some(run) => format!("{} is a run of exactly {}", run_text(run), length),
+                None => {
+                    let lengths = runs
+                        .iter()
+                        .map(|(_, length)| length.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    format!("runs are {} long", lengths)
+                }
+            },
+            Rule::DigitSum { low, high } => {
+                let sum = digits.iter().sum::<u32>();
+                if sum < low {
+                    format!("digits add up to {}, below {}", sum, low)
+                } else if sum > high {
+                    format!("digits add up to {}, above {}", sum, high)
+                } else {
+                    format!("digits add up to {}", sum)
+                }
+            }
+            Rule::ForbidDigit(forbidden) => match digits.iter().position(|&d| d == forbidden) {
+                Some(index) => format!("digit {} is a {}", index + 1, digit_char(forbidden, base)),
+                None => format!("no {}", digit_char(forbidden, base)),
+            },
+        };
+
+        Check {
+            rule: *self,
+            passed: self.check(digits),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rule::Digits(length) => write!(f, "digits({})", length),
+            Rule::NonDecreasing => write!(f, "non_decreasing"),
+            Rule::HasRun { min } => write!(f, "has_run(min={})", min),
+            Rule::HasExactRun(length) => write!(f, "has_exact_run({})", length),
+            Rule::DigitSum { low, high } => write!(f, "digit_sum(in {}..={})", low, high),
+            Rule::ForbidDigit(digit) => write!(f, "forbid_digit({})", digit),
+        }
+    }
+}
+
+/// The most rules a set can have, since counting keeps one bit per rule.
+pub const MAX_RULES: usize = 64;
+
+/// Which codes count, written in `base`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rules {
+    pub base: u32,
+    pub rules: Vec<Rule>,
+}
+
+impl Rules {
+    pub fn part1() -> Rules {
+        Rules {
+            base: 10,
+            rules: vec![
+                Rule::Digits(6),
+                Rule::NonDecreasing,
+                Rule::HasRun { min: 2 },
+            ],
+        }
+    }
+
+    pub fn part2() -> Rules {
+        Rules {
+            base: 10,
+            rules: vec![Rule::Digits(6), Rule::NonDecreasing, Rule::HasExactRun(2)],
+        }
+    }
+
+    /// Checks a single code by looking at its digits.
+    pub fn allows(&self, code: u64) -> bool {
+        let digits = digits(code, self.base);
+        self.rules.iter().all(|rule| rule.check(&digits))
+    }
+
+    /// Checks a single code against each rule in turn, saying why it passed
+    /// or failed.
+    pub fn explain(&self, code: u64) -> Vec<Check> {
+        let digits = digits(code, self.base);
+        self.rules
+            .iter()
+            .map(|rule| rule.explain(&digits, self.base))
+            .collect()
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rules = self
+            .rules
+            .iter()
+            .map(Rule::to_string)
+            .collect::<Vec<String>>()
+            .join(", ");
+        write!(f, "{}", rules)
+    }
+}
+
+/// The digits of `code` in `base`, most significant first.
+pub fn digits(mut code: u64, base: u32) -> Vec<u32> {
+    let base = u64::from(base);
+    let mut digits = vec![(code % base) as u32];
+    code /= base;
+    while code > 0 {
+        digits.push((code % base) as u32);
+        code /= base;
+    }
+    digits.reverse();
+    digits
+}
+
+/// Writes `code` out in `base`, which can be at most 36.
+pub fn format_code(code: u64, base: u32) -> String {
+    digits(code, base)
+        .into_iter()
+        .map(|digit| digit_char(digit, base))
+        .collect()
+}
+
+/// The text between the parentheses after a rule's name, if there are any.
+fn arguments<'a>(input: &str, rule: &'a str, name: &'a str) -> Result<Option<&'a str>, ParseError> {
+    let rest = rule[name.len()..].trim_start();
+    if rest.is_empty() {
+        return Ok(None);
+    }
+    match rest
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        Some(arguments) => Ok(Some(arguments.trim())),
+        None => Err(ParseError::new(
+            input,
+            rest,
+            "expected `(`, then `)` at the end",
+        )),
+    }
+}
+
+/// A number of digits or runs, which has to be at least one.
+fn count(input: &str, text: &str) -> Result<u32, ParseError> {
+    match number(input, text)? {
+        0 => Err(ParseError::new(input, text, "expected at least 1")),
+        count => Ok(count),
+    }
+}
+
+/// Parses `digit_sum`'s range, written `in low..high` or `in low..=high`,
+/// with the `in` optional.
+fn sum_range(input: &str, text: &str) -> Result<Rule, ParseError> {
+    let range = text
+        .strip_prefix("in ")
+        .map(str::trim_start)
+        .unwrap_or(text);
+    let (low, high) = common::parse::split_once(input, range, '.')?;
+    let high = high
+        .strip_prefix('.')
+        .ok_or_else(|| ParseError::new(input, range, "expected `low..high`"))?;
+    let (high, inclusive) = match high.strip_prefix('=') {
+        Some(high) => (high, true),
+        None => (high, false),
+    };
+
+    let low: u32 = number(input, low.trim())?;
+    let high: u32 = number(input, high.trim())?;
+    let high = if inclusive {
+        Some(high)
+    } else {
+        high.checked_sub(1)
+    };
+    match high {
+        Some(high) if low <= high => Ok(Rule::DigitSum { low, high }),
+        _ => Err(ParseError::new(
+            input,
+            range,
+            "expected a range that isn't empty",
+        )),
+    }
+}
+
+fn parse_rule(input: &str, rule: &str, base: u32) -> Result<Rule, ParseError> {
+    let name_length = rule
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(rule.len());
+    let name = &rule[..name_length];
+    let arguments = arguments(input, rule, name)?;
+    let required = || arguments.ok_or_else(|| ParseError::new(input, rule, "expected `(`"));
+
+    let parsed = match name {
+        "digits" => Rule::Digits(count(input, required()?)?),
+        "non_decreasing" => Rule::NonDecreasing,
+        "has_run" => match arguments {
+            None => Rule::HasRun { min: 2 },
+            Some(text) => {
+                let min = text
+                    .strip_prefix("min")
+                    .map(|rest| rest.trim_start())
+                    .and_then(|rest| rest.strip_prefix('='))
+                    .map(str::trim_start)
+                    .unwrap_or(text);
+                Rule::HasRun {
+                    min: count(input, min)?,
+                }
+            }
+        },
+        "has_exact_run" => Rule::HasExactRun(count(input, required()?)?),
+        "digit_sum" => sum_range(input, required()?)?,
+        "forbid_digit" => {
+            let text = required()?;
+            match number(input, text)? {
+                digit if digit < base => Rule::ForbidDigit(digit),
+                _ => {
+                    let message = format!("expected a digit below {}", base);
+                    return Err(ParseError::new(input, text, message));
+                }
+            }
+        }
+        _ => return Err(ParseError::new(input, name, "expected a rule")),
+    };
+
+    match (parsed, arguments) {
+        (Rule::NonDecreasing, Some(text)) => {
+            Err(ParseError::new(input, text, "expected no arguments"))
+        }
+        _ => Ok(parsed),
+    }
+}
+
+/// Parses the rules for codes written in `base`. Anything after a `#` on a
+/// line is a comment.
+pub fn parse(input: &str, base: u32) -> Result<Rules, ParseError> {
+    let texts = input
+        .lines()
+        .map(|line| line.find('#').map_or(line, |comment| &line[..comment]))
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|rule| !rule.is_empty())
+        .collect::<Vec<&str>>();
+    if let Some(extra) = texts.get(MAX_RULES) {
+        let message = format!("expected at most {} rules", MAX_RULES);
+        return Err(ParseError::new(input, extra, message));
+    }
+
+    let rules = texts
+        .into_iter()
+        .map(|rule| parse_rule(input, rule, base))
+        .collect::<Result<Vec<Rule>, ParseError>>()?;
+    Ok(Rules { base, rules })
+}
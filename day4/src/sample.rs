@@ -0,0 +1,121 @@
+//! Picks codes at random with every code following the rules as likely as
+//! any other, by picking positions among them and finding the code at each
+//! position a digit at a time, skipping past as many codes as the counter
+//! says each smaller digit would start.
+
+use crate::count::{narrow, Counter};
+use crate::rules::Rules;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+use std::ops::RangeInclusive;
+
+/// A small xorshift generator, so the same seed always picks the same
+/// codes.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // Xorshift never leaves zero, so mix the seed and keep it odd.
+        Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number below `bound`, each as likely as the others. The bound can
+    /// be at most one past the largest `u64`.
+    pub fn below(&mut self, bound: u128) -> u128 {
+        // Every `u64` is already as likely as the others.
+        let bound = match u64::try_from(bound) {
+            Ok(bound) => bound,
+            Err(_) if bound == 1 << 64 => return u128::from(self.next()),
+            Err(_) => panic!("a bound of at most 2^64"),
+        };
+
+        // Values past the last whole multiple of `bound` would favour the
+        // smallest numbers, so try again when we get one.
+        let limit = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next();
+            if value < limit {
+                return u128::from(value % bound);
+            }
+        }
+    }
+}
+
+/// Finds codes in a range by their position among those following the
+/// rules.
+pub struct Ranking<'a> {
+    counter: Counter<'a>,
+    /// How many codes following the rules come before the range.
+    before: u128,
+    len: u128,
+}
+
+impl<'a> Ranking<'a> {
+    pub fn new(range: RangeInclusive<u64>, rules: &'a Rules) -> Ranking<'a> {
+        let mut counter = Counter::new(rules);
+        let (before, len) = match narrow(range, rules) {
+            Some(range) => {
+                let before = match *range.start() {
+                    0 => 0,
+                    start => counter.up_to(start - 1),
+                };
+                (before, counter.up_to(*range.end()) - before)
+            }
+            None => (0, 0),
+        };
+
+        Ranking {
+            counter,
+            before,
+            len,
+        }
+    }
+
+    /// How many codes in the range follow the rules.
+    pub fn len(&self) -> u128 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The code at `index`, counting from zero, among those in the range
+    /// following the rules.
+    pub fn nth(&mut self, index: u128) -> Option<u64> {
+        if index >= self.len {
+            return None;
+        }
+        self.counter.nth(self.before + index)
+    }
+}
+
+/// Up to `count` different codes in `range` following `rules`, picked at
+/// random and listed from lowest to highest.
+pub fn sample(range: RangeInclusive<u64>, rules: &Rules, count: u64, rng: &mut Rng) -> Vec<u64> {
+    let mut ranking = Ranking::new(range, rules);
+    let total = ranking.len();
+    let count = u128::from(count).min(total);
+
+    // Floyd's algorithm picks `count` different positions, each set of them
+    // as likely as any other.
+    let mut picked = BTreeSet::new();
+    for last in total - count..total {
+        let position = rng.below(last + 1);
+        if !picked.insert(position) {
+            picked.insert(last);
+        }
+    }
+
+    picked
+        .into_iter()
+        .map(|index| ranking.nth(index).expect("a position in the range"))
+        .collect()
+}
@@ -0,0 +1,109 @@
+use day4::count::count;
+use day4::enumerate::codes;
+use day4::rules::{self, Rule, Rules};
+use proptest::prelude::*;
+
+fn rule(base: u32) -> impl Strategy<Value = Rule> {
+    prop_oneof![
+        (1u32..7).prop_map(Rule::Digits),
+        Just(Rule::NonDecreasing),
+        (1u32..4).prop_map(|min| Rule::HasRun { min }),
+        (1u32..4).prop_map(Rule::HasExactRun),
+        (0u32..20, 0u32..20).prop_map(|(low, more)| Rule::DigitSum {
+            low,
+            high: low + more
+        }),
+        (0..base).prop_map(Rule::ForbidDigit),
+    ]
+}
+
+fn rules() -> impl Strategy<Value = Rules> {
+    (2u32..17).prop_flat_map(|base| {
+        prop::collection::vec(rule(base), 0..4).prop_map(move |rules| Rules { base, rules })
+    })
+}
+
+proptest! {
+    #[test]
+    fn counts_match_checking_each_code(
+        rules in rules(),
+        start in 0u64..200_000,
+        length in 0u64..20_000,
+    ) {
+        let range = start..=start + length;
+        let expected = range.clone().filter(|&code| rules.allows(code)).count() as u128;
+        prop_assert_eq!(count(range, &rules), expected);
+    }
+
+    #[test]
+    fn lists_match_checking_each_code(
+        rules in rules(),
+        start in 0u64..200_000,
+        length in 0u64..5_000,
+    ) {
+        let range = start..=start + length;
+        let expected = range.clone().filter(|&code| rules.allows(code)).collect::<Vec<u64>>();
+        prop_assert_eq!(codes(range, &rules).collect::<Vec<u64>>(), expected);
+    }
+
+    #[test]
+    fn rules_read_back_as_written(rules in rules()) {
+        prop_assert_eq!(rules::parse(&rules.to_string(), rules.base).unwrap(), rules);
+    }
+}
+
+#[test]
+fn counts_the_whole_range_of_codes() {
+    let rules = rules::parse("digits(3), non_decreasing", 10).unwrap();
+    // Non-decreasing three digit codes without a leading zero: 9 + 2 choose 3.
+    assert_eq!(count(0..=u64::MAX, &rules), 165);
+}
+
+#[test]
+fn counts_huge_ranges() {
+    let pairs = rules::parse("digits(16), non_decreasing, has_run", 10).unwrap();
+    let all = rules::parse("digits(16), non_decreasing", 10).unwrap();
+    // Sixteen non-decreasing digits from one to nine always repeat one, and
+    // there are 16 + 8 choose 8 of them.
+    assert_eq!(count(0..=10u64.pow(16), &pairs), 735_471);
+    assert_eq!(count(0..=10u64.pow(16), &all), 735_471);
+}
+
+#[test]
+fn reads_rules_with_comments_and_ranges() {
+    let rules = rules::parse(
+        "non_decreasing, has_run(min=3)\n# a comment\ndigit_sum(in 20..30) # another\nforbid_digit(7)",
+        10,
+    )
+    .unwrap();
+    assert_eq!(
+        rules.rules,
+        vec![
+            Rule::NonDecreasing,
+            Rule::HasRun { min: 3 },
+            Rule::DigitSum { low: 20, high: 29 },
+            Rule::ForbidDigit(7),
+        ]
+    );
+    assert!(rules::parse("forbid_digit(7)", 7).is_err());
+    assert!(rules::parse("has_exact_run(0)", 10).is_err());
+    assert!(rules::parse("non_decreasing(2)", 10).is_err());
+
+    let many = vec!["non_decreasing"; 70].join(", ");
+    let error = rules::parse(&many, 10).unwrap_err();
+    assert_eq!(error.message, "expected at most 64 rules");
+    assert_eq!(error.column, 64 * 16 + 1);
+    assert!(rules::parse(&many[..64 * 16 - 2], 10).is_ok());
+}
+
+#[test]
+fn counts_every_code_when_all_of_them_pass() {
+    let rules = rules::parse("has_run(min=1)", 10).unwrap();
+    assert_eq!(count(0..=u64::MAX, &rules), 1 << 64);
+    assert_eq!(count(1..=u64::MAX, &rules), u128::from(u64::MAX));
+    let none = Rules {
+        base: 2,
+        rules: Vec::new(),
+    };
+    assert_eq!(count(0..=u64::MAX, &none), 1 << 64);
+}
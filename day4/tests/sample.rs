@@ -0,0 +1,83 @@
+use day4::enumerate::codes;
+use day4::rules::{self, Rules};
+use day4::sample::{sample, Ranking, Rng};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn finds_codes_by_position(start in 0u64..1_000_000, length in 0u64..50_000) {
+        let rules = Rules::part2();
+        let range = start..=start + length;
+        let mut ranking = Ranking::new(range.clone(), &rules);
+        let listed = codes(range, &rules).collect::<Vec<u64>>();
+        prop_assert_eq!(ranking.len(), listed.len() as u128);
+        for (index, &code) in listed.iter().enumerate() {
+            prop_assert_eq!(ranking.nth(index as u128), Some(code));
+        }
+        prop_assert_eq!(ranking.nth(listed.len() as u128), None);
+    }
+
+    #[test]
+    fn samples_different_codes_following_the_rules(seed: u64, count in 0u64..50) {
+        let rules = rules::parse("non_decreasing, has_exact_run(2)", 10).unwrap();
+        let picked = sample(0..=99_999, &rules, count, &mut Rng::new(seed));
+        prop_assert_eq!(picked.len() as u64, count);
+        prop_assert!(picked.windows(2).all(|pair| pair[0] < pair[1]));
+        prop_assert!(picked.iter().all(|&code| rules.allows(code)));
+        prop_assert_eq!(&picked, &sample(0..=99_999, &rules, count, &mut Rng::new(seed)));
+    }
+}
+
+#[test]
+fn samples_every_code_when_asked_for_more() {
+    let rules = Rules::part1();
+    let range = 108_457..=112_000;
+    let all = codes(range.clone(), &rules).collect::<Vec<u64>>();
+    assert_eq!(sample(range, &rules, 1_000_000, &mut Rng::new(1)), all);
+}
+
+#[test]
+fn samples_each_code_about_as_often() {
+    let rules = rules::parse("digits(2), non_decreasing", 10).unwrap();
+    let mut rng = Rng::new(2019);
+    let mut times = [0u32; 100];
+    for _ in 0..9_000 {
+        for code in sample(0..=99, &rules, 1, &mut rng) {
+            times[code as usize] += 1;
+        }
+    }
+
+    // 45 codes, each picked about two hundred times.
+    let picked = times
+        .iter()
+        .filter(|&&count| count > 0)
+        .collect::<Vec<&u32>>();
+    assert_eq!(picked.len(), 45);
+    assert!(picked.iter().all(|&&count| (130..270).contains(&count)));
+}
+
+#[test]
+fn finds_codes_up_to_the_largest_u64() {
+    let rules = rules::parse("has_run", 10).unwrap();
+    let mut ranking = Ranking::new(0..=u64::MAX, &rules);
+    let last = ranking.len() - 1;
+    assert_eq!(ranking.nth(last), Some(u64::MAX));
+    assert_eq!(ranking.nth(last + 1), None);
+
+    let picked = sample(0..=u64::MAX, &rules, 2, &mut Rng::new(1));
+    assert!(picked.iter().all(|&code| rules.allows(code)));
+}
+
+#[test]
+fn finds_codes_when_every_u64_passes() {
+    let rules = rules::parse("has_run(min=1)", 10).unwrap();
+    let mut ranking = Ranking::new(0..=u64::MAX, &rules);
+    assert_eq!(ranking.len(), 1 << 64);
+    assert_eq!(ranking.nth(0), Some(0));
+    assert_eq!(ranking.nth(12_345), Some(12_345));
+    assert_eq!(ranking.nth(u128::from(u64::MAX)), Some(u64::MAX));
+    assert_eq!(ranking.nth(1 << 64), None);
+
+    let picked = sample(0..=u64::MAX, &rules, 3, &mut Rng::new(1));
+    assert_eq!(picked.len(), 3);
+}
@@ -0,0 +1,41 @@
+use common::{ParseError, Solution};
+use intcode::Computer;
+
+fn diagnostic_code(memory: &[i64], system: i64) -> i64 {
+    let mut computer = Computer::new(memory);
+    computer.inputs.push_back(system);
+    let outputs = computer.run_until_blocked().expect("a working program");
+    *outputs.last().expect("diagnostic code")
+}
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    intcode::parse_program(input)
+}
+
+pub fn part1(memory: &[i64]) -> i64 {
+    diagnostic_code(memory, 1)
+}
+
+pub fn part2(memory: &[i64]) -> i64 {
+    diagnostic_code(memory, 5)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<i64>) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<i64>) -> i64 {
+        part2(input)
+    }
+}
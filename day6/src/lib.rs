@@ -0,0 +1,73 @@
+pub mod orbit_map;
+pub mod render;
+pub mod validate;
+
+use common::{ParseError, Solution};
+use orbit_map::{OrbitMap, OrbitMapBuilder};
+
+/// The body everything orbits in the end.
+pub const ROOT: &str = "COM";
+
+/// Parses the orbits, failing at the line completing the first problem
+/// that keeps them from being a map. More than one system, or bodies not
+/// orbiting `COM`, are fine here; `validate` reports them.
+pub fn parse(input: &str) -> Result<OrbitMap, ParseError> {
+    let orbits = validate::orbits(input)?;
+    let problems = validate::validate(&orbits, ROOT);
+    let fatal = problems
+        .iter()
+        .filter(|problem| problem.is_fatal())
+        .min_by_key(|problem| problem.lines().last());
+    if let Some(problem) = fatal {
+        let line = problem.lines().last().expect("a line at fault");
+        let orbit = orbits
+            .iter()
+            .find(|orbit| orbit.line == *line)
+            .expect("an orbit on the line");
+        return Err(ParseError::new(input, orbit.text, problem.to_string()));
+    }
+
+    let mut builder = OrbitMapBuilder::new();
+    for orbit in &orbits {
+        builder.add(orbit.parent, orbit.child);
+    }
+    Ok(builder.build())
+}
+
+pub fn part1(orbits: &OrbitMap) -> usize {
+    orbits.total_orbits()
+}
+
+/// Transfers from `YOU` to Santa, unless the map leaves either of them out
+/// or there's no way between them.
+pub fn part2(orbits: &OrbitMap) -> Result<usize, String> {
+    let body = |name| {
+        orbits
+            .body(name)
+            .ok_or_else(|| format!("there is no {} in the map", name))
+    };
+    let (you, santa) = (body("YOU")?, body("SAN")?);
+    orbits
+        .transfers(you, santa)
+        .ok_or_else(|| "there's no way from YOU to SAN".to_string())
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = OrbitMap;
+    type Part1 = usize;
+    type Part2 = Result<usize, String>;
+
+    fn parse(input: &str) -> Result<OrbitMap, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &OrbitMap) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &OrbitMap) -> Result<usize, String> {
+        part2(input)
+    }
+}
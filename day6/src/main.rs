@@ -1,60 +1,134 @@
-use std::collections::{HashMap, HashSet};
-use std::io::{self, BufRead};
-use std::iter;
+use clap::Parser;
+use day6::orbit_map::{Body, OrbitMap};
+use day6::render::{self, Format};
+use day6::{validate, Day6};
+use std::io::{self, Read};
+use std::process;
 
-fn part1(orbits: &HashMap<String, String>) {
-    let answer: usize = orbits
-        .keys()
-        .map(|start| iter::successors(Some(start), |prev| orbits.get(prev.as_str())).count() - 1)
-        .sum();
+#[derive(Debug, Parser)]
+struct Options {
+    /// List every problem with the orbits, such as cycles or bodies not
+    /// orbiting `COM`, instead of answering the puzzle
+    #[arg(long)]
+    check: bool,
 
-    println!("part 1 = {}", answer);
+    /// Show the deepest body two bodies share, and how far apart they are
+    #[arg(long, num_args = 2, value_names = ["BODY", "BODY"])]
+    between: Option<Vec<String>>,
+
+    /// Show how many bodies orbit a body, directly or not
+    #[arg(long, value_name = "BODY")]
+    below: Option<String>,
+
+    /// Draw the orbits instead of answering the puzzle: `tree`, `dot` for
+    /// Graphviz or `json`
+    #[arg(long, value_name = "FORMAT")]
+    render: Option<Format>,
+
+    /// The route to mark when drawing, `YOU` to `SAN` if both are there
+    #[arg(long, num_args = 2, value_names = ["FROM", "TO"])]
+    route: Option<Vec<String>>,
+
+    /// Draw only the route
+    #[arg(long, requires = "render")]
+    route_only: bool,
+
+    /// Draw chains of bodies, each orbited by just the next, as one
+    #[arg(long, requires = "render")]
+    collapse: bool,
 }
 
-fn part2(orbits: &HashMap<String, String>) {
-    let you_orbit = iter::successors(Some("YOU".to_string()), |prev| {
-        orbits.get(prev.as_str()).cloned()
+fn body(orbits: &OrbitMap, name: &str) -> Body {
+    orbits.body(name).unwrap_or_else(|| {
+        eprintln!("No body called `{}`", name);
+        process::exit(2);
     })
-    .collect::<Vec<String>>();
-    let san_orbit = iter::successors(Some("SAN".to_string()), |prev| {
-        orbits.get(prev.as_str()).cloned()
-    })
-    .collect::<Vec<String>>();
-
-    let san_ancestors = san_orbit.iter().cloned().collect::<HashSet<String>>();
-    let ancestor = you_orbit
-        .iter()
-        .find(|orbit| san_ancestors.contains(orbit.as_str()))
-        .unwrap();
-
-    let transwers_to_ancestor = you_orbit
-        .iter()
-        .take_while(|orbit| orbit != &ancestor)
-        .count()
-        - 1;
-    let transwers_from_ancestor = san_orbit
-        .iter()
-        .take_while(|orbit| orbit != &ancestor)
-        .count()
-        - 1;
-    let answer = transwers_to_ancestor + transwers_from_ancestor;
-
-    println!("part 2 = {}", answer);
 }
 
-fn main() {
-    let input = io::stdin()
+fn check() {
+    let mut input = String::new();
+    io::stdin()
         .lock()
-        .lines()
-        .map(|line| line.expect("Failed to read data"))
-        .map(|line| {
-            let mut parts = line.split(')');
-            let value = parts.next().expect("value").to_string();
-            let key = parts.next().expect("key").to_string();
-            (key, value)
-        })
-        .collect::<HashMap<String, String>>();
-
-    part1(&input);
-    part2(&input);
+        .read_to_string(&mut input)
+        .expect("Failed to read data");
+    let orbits = validate::orbits(&input).unwrap_or_else(|e| {
+        eprintln!("Failed to parse input: {}", e);
+        process::exit(1);
+    });
+
+    let problems = validate::validate(&orbits, day6::ROOT);
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    if problems.is_empty() {
+        println!("no problems with {} orbits", orbits.len());
+    } else {
+        process::exit(1);
+    }
+}
+
+fn draw(orbits: &OrbitMap, format: Format, options: &Options) {
+    let route = match &options.route {
+        Some(names) => {
+            let (from, to) = (body(orbits, &names[0]), body(orbits, &names[1]));
+            let route = render::route(orbits, from, to);
+            if route.is_none() {
+                eprintln!("{} and {} share no body", names[0], names[1]);
+                process::exit(2);
+            }
+            route
+        }
+        None => match (orbits.body("YOU"), orbits.body("SAN")) {
+            (Some(you), Some(santa)) => render::route(orbits, you, santa),
+            _ => None,
+        },
+    };
+    if options.route_only && route.is_none() {
+        eprintln!("No route to draw; give one with `--route`");
+        process::exit(2);
+    }
+
+    let options = render::Options {
+        route,
+        route_only: options.route_only,
+        collapse: options.collapse,
+    };
+    print!("{}", render::render(orbits, format, &options));
+}
+
+fn main() {
+    let options = Options::parse();
+    if options.check {
+        return check();
+    }
+
+    let orbits = common::read_input::<Day6>();
+    if let Some(format) = options.render {
+        return draw(&orbits, format, &options);
+    }
+    common::print_answers::<Day6>(&orbits);
+
+    if let Some(names) = &options.between {
+        let (a, b) = (body(&orbits, &names[0]), body(&orbits, &names[1]));
+        match orbits.common_ancestor(a, b) {
+            Some(common) => {
+                println!("common ancestor = {}", orbits.name(common));
+                println!(
+                    "distance = {}",
+                    orbits.distance(a, b).expect("a shared body")
+                );
+                if let Some(transfers) = orbits.transfers(a, b) {
+                    println!("transfers = {}", transfers);
+                }
+            }
+            None => println!("{} and {} share no body", names[0], names[1]),
+        }
+    }
+
+    if let Some(name) = &options.below {
+        let below = body(&orbits, name);
+        println!("depth = {}", orbits.depth(below));
+        println!("orbiting = {}", orbits.subtree_size(below) - 1);
+        println!("directly = {}", orbits.children(below).len());
+    }
 }
@@ -0,0 +1,263 @@
+//! Bodies and what they orbit, with the depth of every body, the size of
+//! everything orbiting it and a table of ancestors worked out once up
+//! front, so queries don't have to walk the chain of orbits each time.
+
+use std::collections::{HashMap, VecDeque};
+
+/// A body in an `OrbitMap`, standing in for its name.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Body(usize);
+
+impl Body {
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Collects orbits before working everything out in `build`.
+#[derive(Debug, Default)]
+pub struct OrbitMapBuilder {
+    names: Vec<String>,
+    ids: HashMap<String, Body>,
+    parents: Vec<Option<Body>>,
+}
+
+impl OrbitMapBuilder {
+    pub fn new() -> OrbitMapBuilder {
+        OrbitMapBuilder::default()
+    }
+
+    /// The body called `name`, adding it if it's new.
+    pub fn intern(&mut self, name: &str) -> Body {
+        if let Some(&body) = self.ids.get(name) {
+            return body;
+        }
+        let body = Body(self.names.len());
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), body);
+        self.parents.push(None);
+        body
+    }
+
+    /// Records that `child` orbits `parent`, replacing anything it was
+    /// said to orbit before.
+    pub fn add(&mut self, parent: &str, child: &str) -> &mut OrbitMapBuilder {
+        let parent = self.intern(parent);
+        let child = self.intern(child);
+        self.parents[child.0] = Some(parent);
+        self
+    }
+
+    /// Works out depths, sizes and ancestors.
+    ///
+    /// # Panics
+    ///
+    /// If some of the orbits form a cycle.
+    pub fn build(self) -> OrbitMap {
+        let count = self.names.len();
+        let mut children = vec![Vec::new(); count];
+        for (child, parent) in self.parents.iter().enumerate() {
+            if let Some(parent) = parent {
+                children[parent.0].push(Body(child));
+            }
+        }
+
+        // Every body comes after what it orbits.
+        let mut order = Vec::with_capacity(count);
+        let mut queue = (0..count)
+            .filter(|&body| self.parents[body].is_none())
+            .map(Body)
+            .collect::<VecDeque<Body>>();
+        let mut depths = vec![0; count];
+        let mut root_of = (0..count).map(Body).collect::<Vec<Body>>();
+        while let Some(body) = queue.pop_front() {
+            order.push(body);
+            for &child in &children[body.0] {
+                depths[child.0] = depths[body.0] + 1;
+                root_of[child.0] = root_of[body.0];
+                queue.push_back(child);
+            }
+        }
+        assert_eq!(order.len(), count, "orbits form a cycle");
+
+        let mut sizes = vec![1; count];
+        for &body in order.iter().rev() {
+            if let Some(parent) = self.parents[body.0] {
+                sizes[parent.0] += sizes[body.0];
+            }
+        }
+
+        // `jumps[k][body]` is 2^k orbits out from `body`, stopping at the
+        // body everything it orbits around.
+        let deepest = depths.iter().copied().max().unwrap_or(0);
+        let mut jumps = vec![(0..count)
+            .map(|body| self.parents[body].unwrap_or(Body(body)))
+            .collect::<Vec<Body>>()];
+        while 1 << jumps.len() <= deepest {
+            let last = jumps.last().expect("the first jump");
+            let next = last.iter().map(|&body| last[body.0]).collect();
+            jumps.push(next);
+        }
+
+        OrbitMap {
+            names: self.names,
+            ids: self.ids,
+            parents: self.parents,
+            children,
+            root_of,
+            depths,
+            sizes,
+            jumps,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OrbitMap {
+    names: Vec<String>,
+    ids: HashMap<String, Body>,
+    parents: Vec<Option<Body>>,
+    children: Vec<Vec<Body>>,
+    root_of: Vec<Body>,
+    depths: Vec<usize>,
+    sizes: Vec<usize>,
+    jumps: Vec<Vec<Body>>,
+}
+
+impl OrbitMap {
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn body(&self, name: &str) -> Option<Body> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, body: Body) -> &str {
+        &self.names[body.0]
+    }
+
+    pub fn bodies(&self) -> impl Iterator<Item = Body> {
+        (0..self.len()).map(Body)
+    }
+
+    /// What `body` directly orbits.
+    pub fn parent(&self, body: Body) -> Option<Body> {
+        self.parents[body.0]
+    }
+
+    /// The bodies directly orbiting `body`.
+    pub fn children(&self, body: Body) -> &[Body] {
+        &self.children[body.0]
+    }
+
+    /// The bodies that don't orbit anything.
+    pub fn roots(&self) -> impl Iterator<Item = Body> + '_ {
+        self.bodies()
+            .filter(move |&body| self.parent(body).is_none())
+    }
+
+    /// The body at the centre of everything `body` orbits.
+    pub fn root(&self, body: Body) -> Body {
+        self.root_of[body.0]
+    }
+
+    /// How many bodies `body` orbits, directly and indirectly.
+    pub fn depth(&self, body: Body) -> usize {
+        self.depths[body.0]
+    }
+
+    /// Everything `body` orbits, from what it orbits directly outwards.
+    pub fn ancestors(&self, body: Body) -> impl Iterator<Item = Body> + '_ {
+        std::iter::successors(self.parent(body), move |&body| self.parent(body))
+    }
+
+    /// `body` and every body orbiting it, directly or not.
+    pub fn subtree_size(&self, body: Body) -> usize {
+        self.sizes[body.0]
+    }
+
+    /// Every body orbiting `body`, directly or not, each before anything
+    /// orbiting it.
+    pub fn descendants(&self, body: Body) -> impl Iterator<Item = Body> + '_ {
+        let mut stack = self
+            .children(body)
+            .iter()
+            .rev()
+            .copied()
+            .collect::<Vec<Body>>();
+        std::iter::from_fn(move || {
+            let body = stack.pop()?;
+            stack.extend(self.children(body).iter().rev());
+            Some(body)
+        })
+    }
+
+    /// The body `steps` orbits out from `body`, which must orbit at least
+    /// that many.
+    fn ancestor_at(&self, mut body: Body, steps: usize) -> Body {
+        for (level, jumps) in self.jumps.iter().enumerate() {
+            if steps & 1 << level != 0 {
+                body = jumps[body.0];
+            }
+        }
+        body
+    }
+
+    /// The deepest body that both `a` and `b` are or orbit, if they're
+    /// part of the same system.
+    pub fn common_ancestor(&self, a: Body, b: Body) -> Option<Body> {
+        if self.root(a) != self.root(b) {
+            return None;
+        }
+
+        let (depth_a, depth_b) = (self.depth(a), self.depth(b));
+        let mut a = self.ancestor_at(a, depth_a.saturating_sub(depth_b));
+        let mut b = self.ancestor_at(b, depth_b.saturating_sub(depth_a));
+        if a == b {
+            return Some(a);
+        }
+        for jumps in self.jumps.iter().rev() {
+            if jumps[a.0] != jumps[b.0] {
+                a = jumps[a.0];
+                b = jumps[b.0];
+            }
+        }
+        self.parent(a)
+    }
+
+    /// How many orbits apart `a` and `b` are, going through the deepest
+    /// body they share.
+    pub fn distance(&self, a: Body, b: Body) -> Option<usize> {
+        let common = self.common_ancestor(a, b)?;
+        Some(self.depth(a) + self.depth(b) - 2 * self.depth(common))
+    }
+
+    /// Orbital transfers needed to move from the body `a` orbits to the
+    /// one `b` orbits.
+    pub fn transfers(&self, a: Body, b: Body) -> Option<usize> {
+        self.distance(self.parent(a)?, self.parent(b)?)
+    }
+
+    /// How many bodies orbit another directly.
+    pub fn direct_orbits(&self) -> usize {
+        self.parents
+            .iter()
+            .filter(|parent| parent.is_some())
+            .count()
+    }
+
+    /// How many bodies orbit another indirectly, through something in
+    /// between, counting a body once for each.
+    pub fn indirect_orbits(&self) -> usize {
+        self.total_orbits() - self.direct_orbits()
+    }
+
+    pub fn total_orbits(&self) -> usize {
+        self.depths.iter().sum()
+    }
+}
@@ -0,0 +1,277 @@
+//! Draws the orbit map as an indented tree, as Graphviz DOT or as JSON,
+//! marking the route between two bodies. Maps can be cut down to just the
+//! route, and chains of bodies each orbited by a single body drawn as one.
+
+use crate::orbit_map::{Body, OrbitMap};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Chains shorter than this are drawn body by body even when collapsing.
+const SHORTEST_COLLAPSED: usize = 3;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Format {
+    Tree,
+    Dot,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "tree" => Ok(Format::Tree),
+            "dot" => Ok(Format::Dot),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("expected `tree`, `dot` or `json`, found `{}`", s)),
+        }
+    }
+}
+
+/// The bodies from `from` to `to`, both included, going through the
+/// deepest body they share.
+pub fn route(orbits: &OrbitMap, from: Body, to: Body) -> Option<Vec<Body>> {
+    let common = orbits.common_ancestor(from, to)?;
+    let up = |body| {
+        std::iter::once(body)
+            .chain(orbits.ancestors(body))
+            .take_while(move |&body| body != common)
+    };
+    let mut bodies = up(from).collect::<Vec<Body>>();
+    bodies.push(common);
+    bodies.extend(up(to).collect::<Vec<Body>>().into_iter().rev());
+    Some(bodies)
+}
+
+/// How to cut down and mark the map being drawn.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Bodies to mark, from one end of a route to the other.
+    pub route: Option<Vec<Body>>,
+    /// Draw only the route.
+    pub route_only: bool,
+    /// Draw chains of bodies, each orbited by just the next, as one.
+    pub collapse: bool,
+}
+
+/// A body to draw, or a chain of them drawn as one, with what orbits the
+/// last of them.
+#[derive(Clone, Debug, PartialEq)]
+struct Node {
+    bodies: Vec<Body>,
+    on_route: bool,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn label(&self, orbits: &OrbitMap) -> String {
+        match &self.bodies[..] {
+            [body] => orbits.name(*body).to_string(),
+            [first, .., last] => format!(
+                "{} … {} ({} bodies)",
+                orbits.name(*first),
+                orbits.name(*last),
+                self.bodies.len()
+            ),
+            [] => unreachable!(),
+        }
+    }
+}
+
+struct Builder<'a> {
+    orbits: &'a OrbitMap,
+    options: &'a Options,
+    on_route: HashSet<Body>,
+}
+
+impl<'a> Builder<'a> {
+    fn children(&self, body: Body) -> Vec<Body> {
+        self.orbits
+            .children(body)
+            .iter()
+            .copied()
+            .filter(|child| !self.options.route_only || self.on_route.contains(child))
+            .collect()
+    }
+
+    fn node(&self, body: Body) -> Node {
+        let mut bodies = vec![body];
+        let mut children = self.children(body);
+        if self.options.collapse {
+            let mut chain = bodies.clone();
+            let mut below = children.clone();
+            while let [only] = below[..] {
+                chain.push(only);
+                below = self.children(only);
+            }
+            if chain.len() >= SHORTEST_COLLAPSED {
+                bodies = chain;
+                children = below;
+            }
+        }
+
+        Node {
+            on_route: bodies.iter().any(|body| self.on_route.contains(body)),
+            children: children.into_iter().map(|child| self.node(child)).collect(),
+            bodies,
+        }
+    }
+}
+
+fn nodes(orbits: &OrbitMap, options: &Options) -> Vec<Node> {
+    let route = options.route.as_deref().unwrap_or(&[]);
+    let builder = Builder {
+        orbits,
+        options,
+        on_route: route.iter().copied().collect(),
+    };
+
+    if options.route_only {
+        // The route's highest body is the one both ends orbit.
+        let top = route.iter().min_by_key(|&&body| orbits.depth(body));
+        top.map(|&top| builder.node(top)).into_iter().collect()
+    } else {
+        orbits.roots().map(|root| builder.node(root)).collect()
+    }
+}
+
+fn write_tree(node: &Node, orbits: &OrbitMap, prefix: &str, branch: &str, out: &mut String) {
+    let mark = if node.on_route { " *" } else { "" };
+    writeln!(out, "{}{}{}{}", prefix, branch, node.label(orbits), mark).unwrap();
+
+    let prefix = match branch {
+        "├── " => format!("{}│   ", prefix),
+        "└── " => format!("{}    ", prefix),
+        _ => prefix.to_string(),
+    };
+    for (index, child) in node.children.iter().enumerate() {
+        let branch = if index + 1 == node.children.len() {
+            "└── "
+        } else {
+            "├── "
+        };
+        write_tree(child, orbits, &prefix, branch, out);
+    }
+}
+
+fn tree(nodes: &[Node], orbits: &OrbitMap, options: &Options) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        write_tree(node, orbits, "", "", &mut out);
+    }
+    if let Some(route) = &options.route {
+        let (from, to) = (route[0], route[route.len() - 1]);
+        write!(
+            out,
+            "* marks the route from {} to {}",
+            orbits.name(from),
+            orbits.name(to)
+        )
+        .unwrap();
+        // Transfers go between what each end orbits, so there are none to
+        // count when an end orbits nothing.
+        match orbits.transfers(from, to) {
+            Some(transfers) => writeln!(out, ", {} transfers", transfers).unwrap(),
+            None => out.push('\n'),
+        }
+    }
+    out
+}
+
+/// Quotes `text` as a DOT string.
+fn quoted(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn write_dot(node: &Node, orbits: &OrbitMap, out: &mut String) {
+    let id = node.bodies[0].index();
+    let mut attributes = vec![format!("label={}", quoted(&node.label(orbits)))];
+    if node.bodies.len() > 1 {
+        attributes.push("shape=box".to_string());
+    }
+    if node.on_route {
+        attributes.push("color=red, penwidth=2".to_string());
+    }
+    writeln!(out, "    n{} [{}];", id, attributes.join(", ")).unwrap();
+
+    for child in &node.children {
+        let style = if node.on_route && child.on_route {
+            " [color=red, penwidth=2]"
+        } else {
+            ""
+        };
+        writeln!(out, "    n{} -> n{}{};", id, child.bodies[0].index(), style).unwrap();
+        write_dot(child, orbits, out);
+    }
+}
+
+fn dot(nodes: &[Node], orbits: &OrbitMap) -> String {
+    let mut out = String::from("digraph orbits {\n    rankdir=LR;\n");
+    for node in nodes {
+        write_dot(node, orbits, &mut out);
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[derive(Serialize)]
+struct JsonNode<'a> {
+    bodies: Vec<&'a str>,
+    on_route: bool,
+    children: Vec<JsonNode<'a>>,
+}
+
+impl<'a> JsonNode<'a> {
+    fn new(node: &Node, orbits: &'a OrbitMap) -> JsonNode<'a> {
+        JsonNode {
+            bodies: node.bodies.iter().map(|&body| orbits.name(body)).collect(),
+            on_route: node.on_route,
+            children: node
+                .children
+                .iter()
+                .map(|child| JsonNode::new(child, orbits))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRoute<'a> {
+    bodies: Vec<&'a str>,
+    transfers: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct JsonMap<'a> {
+    roots: Vec<JsonNode<'a>>,
+    route: Option<JsonRoute<'a>>,
+}
+
+fn json(nodes: &[Node], orbits: &OrbitMap, options: &Options) -> String {
+    let map = JsonMap {
+        roots: nodes
+            .iter()
+            .map(|node| JsonNode::new(node, orbits))
+            .collect(),
+        route: options.route.as_ref().map(|route| JsonRoute {
+            bodies: route.iter().map(|&body| orbits.name(body)).collect(),
+            transfers: orbits.transfers(route[0], route[route.len() - 1]),
+        }),
+    };
+    let mut out = serde_json::to_string_pretty(&map).expect("orbits as JSON");
+    out.push('\n');
+    out
+}
+
+/// Draws `orbits` in `format`, cut down and marked as `options` say.
+pub fn render(orbits: &OrbitMap, format: Format, options: &Options) -> String {
+    let nodes = nodes(orbits, options);
+    match format {
+        Format::Tree => tree(&nodes, orbits, options),
+        Format::Dot => dot(&nodes, orbits),
+        Format::Json => json(&nodes, orbits, options),
+    }
+}
@@ -0,0 +1,297 @@
+//! Finds everything wrong with a list of orbits at once, pointing at the
+//! lines responsible: bodies orbiting more than one body, orbits going
+//! round in a cycle, more than one body at the centre and bodies that
+//! don't end up orbiting the centre.
+
+use common::parse::split_once;
+use common::ParseError;
+use std::collections::HashMap;
+use std::fmt;
+
+/// One line of the input: `child` orbits `parent`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Orbit<'a> {
+    pub parent: &'a str,
+    pub child: &'a str,
+    /// 1-based line number.
+    pub line: usize,
+    pub text: &'a str,
+}
+
+/// Reads each line as an orbit, without checking how they fit together.
+pub fn orbits(input: &str) -> Result<Vec<Orbit<'_>>, ParseError> {
+    let mut orbits = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let text = line.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let (parent, child) = split_once(input, text, ')')?;
+        for name in &[parent, child] {
+            if name.is_empty() || !name.chars().all(char::is_alphanumeric) {
+                return Err(ParseError::new(input, name, "expected a body name"));
+            }
+        }
+        orbits.push(Orbit {
+            parent,
+            child,
+            line: index + 1,
+            text,
+        });
+    }
+    Ok(orbits)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    /// A body said to orbit more than one body, on `lines`.
+    SeveralParents { body: String, lines: Vec<usize> },
+    /// Bodies orbiting each other in a circle, through the orbits on
+    /// `lines`.
+    Cycle {
+        bodies: Vec<String>,
+        lines: Vec<usize>,
+    },
+    /// No body with the name everything should orbit.
+    MissingRoot { body: String },
+    /// A body orbiting nothing besides the centre, orbited on `lines`.
+    ExtraRoot { body: String, lines: Vec<usize> },
+    /// A body that doesn't end up orbiting `root`, given what it orbits on
+    /// `lines`.
+    Unreachable {
+        body: String,
+        root: String,
+        lines: Vec<usize>,
+    },
+}
+
+impl Problem {
+    /// Whether the orbits can't be made into a map at all, rather than
+    /// being a map of more than one system.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, Problem::SeveralParents { .. } | Problem::Cycle { .. })
+    }
+
+    pub fn lines(&self) -> &[usize] {
+        match self {
+            Problem::SeveralParents { lines, .. }
+            | Problem::Cycle { lines, .. }
+            | Problem::ExtraRoot { lines, .. }
+            | Problem::Unreachable { lines, .. } => lines,
+            Problem::MissingRoot { .. } => &[],
+        }
+    }
+}
+
+/// `line 3`, `lines 3 and 5` or `lines 3, 5 and 8`.
+fn describe_lines(lines: &[usize]) -> String {
+    let numbers = lines.iter().map(usize::to_string).collect::<Vec<String>>();
+    match numbers.split_last() {
+        None => "no lines".to_string(),
+        Some((last, [])) => format!("line {}", last),
+        Some((last, rest)) => format!("lines {} and {}", rest.join(", "), last),
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::SeveralParents { body, lines } => write!(
+                f,
+                "`{}` orbits more than one body, on {}",
+                body,
+                describe_lines(lines)
+            ),
+            Problem::Cycle { bodies, lines } if bodies.len() == 1 => write!(
+                f,
+                "`{}` orbits itself, on {}",
+                bodies[0],
+                describe_lines(lines)
+            ),
+            Problem::Cycle { bodies, lines } => write!(
+                f,
+                "`{}` orbit each other in a cycle, on {}",
+                bodies.join("`, `"),
+                describe_lines(lines)
+            ),
+            Problem::MissingRoot { body } => write!(f, "there's no `{}`", body),
+            Problem::ExtraRoot { body, lines } => write!(
+                f,
+                "`{}` doesn't orbit anything, but is orbited on {}",
+                body,
+                describe_lines(lines)
+            ),
+            Problem::Unreachable { body, root, lines } => write!(
+                f,
+                "`{}` doesn't end up orbiting `{}`, going by {}",
+                body,
+                root,
+                describe_lines(lines)
+            ),
+        }
+    }
+}
+
+/// The bodies in each cycle of orbits, found as the strongly connected
+/// components of more than one body, or of a body orbiting itself, with
+/// Tarjan's algorithm.
+fn cycles(count: usize, children: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut index = vec![None; count];
+    let mut lowest = vec![0; count];
+    let mut on_stack = vec![false; count];
+    let mut stack = Vec::new();
+    let mut next = 0;
+    let mut found = Vec::new();
+
+    for start in 0..count {
+        if index[start].is_some() {
+            continue;
+        }
+
+        // Bodies being visited, with how many of their children are done.
+        let mut visiting = vec![(start, 0)];
+        index[start] = Some(next);
+        lowest[start] = next;
+        next += 1;
+        stack.push(start);
+        on_stack[start] = true;
+
+        while let Some(&mut (body, ref mut child)) = visiting.last_mut() {
+            if let Some(&other) = children[body].get(*child) {
+                *child += 1;
+                match index[other] {
+                    None => {
+                        index[other] = Some(next);
+                        lowest[other] = next;
+                        next += 1;
+                        stack.push(other);
+                        on_stack[other] = true;
+                        visiting.push((other, 0));
+                    }
+                    Some(other_index) if on_stack[other] => {
+                        lowest[body] = lowest[body].min(other_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            visiting.pop();
+            if let Some(&(parent, _)) = visiting.last() {
+                lowest[parent] = lowest[parent].min(lowest[body]);
+            }
+            if Some(lowest[body]) == index[body] {
+                let mut component = Vec::new();
+                loop {
+                    let member = stack.pop().expect("the body itself");
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == body {
+                        break;
+                    }
+                }
+                if component.len() > 1 || children[body].contains(&body) {
+                    component.reverse();
+                    found.push(component);
+                }
+            }
+        }
+    }
+    found
+}
+
+/// Everything wrong with `orbits`, taking `root` as the body everything
+/// should orbit in the end.
+pub fn validate(orbits: &[Orbit], root: &str) -> Vec<Problem> {
+    let mut names: Vec<&str> = Vec::new();
+    let mut ids: HashMap<&str, usize> = HashMap::new();
+    let mut intern = |name| {
+        *ids.entry(name).or_insert_with(|| {
+            names.push(name);
+            names.len() - 1
+        })
+    };
+    let edges = orbits
+        .iter()
+        .map(|orbit| (intern(orbit.parent), intern(orbit.child), orbit.line))
+        .collect::<Vec<(usize, usize, usize)>>();
+    let count = names.len();
+
+    let mut children = vec![Vec::new(); count];
+    let mut parent_lines = vec![Vec::new(); count];
+    let mut child_lines = vec![Vec::new(); count];
+    for &(parent, child, line) in &edges {
+        children[parent].push(child);
+        parent_lines[child].push(line);
+        child_lines[parent].push(line);
+    }
+
+    let mut problems = Vec::new();
+    for body in 0..count {
+        if parent_lines[body].len() > 1 {
+            problems.push(Problem::SeveralParents {
+                body: names[body].to_string(),
+                lines: parent_lines[body].clone(),
+            });
+        }
+    }
+
+    let mut in_cycle = vec![false; count];
+    for cycle in cycles(count, &children) {
+        for &body in &cycle {
+            in_cycle[body] = true;
+        }
+        let lines = edges
+            .iter()
+            .filter(|&&(parent, child, _)| cycle.contains(&parent) && cycle.contains(&child))
+            .map(|&(_, _, line)| line)
+            .collect();
+        problems.push(Problem::Cycle {
+            bodies: cycle.iter().map(|&body| names[body].to_string()).collect(),
+            lines,
+        });
+    }
+
+    for body in 0..count {
+        if parent_lines[body].is_empty() && names[body] != root {
+            problems.push(Problem::ExtraRoot {
+                body: names[body].to_string(),
+                lines: child_lines[body].clone(),
+            });
+        }
+    }
+
+    let centre = match ids.get(root) {
+        Some(&centre) => centre,
+        None => {
+            problems.push(Problem::MissingRoot {
+                body: root.to_string(),
+            });
+            return problems;
+        }
+    };
+    let mut reached = vec![false; count];
+    let mut stack = vec![centre];
+    reached[centre] = true;
+    while let Some(body) = stack.pop() {
+        for &child in &children[body] {
+            if !reached[child] {
+                reached[child] = true;
+                stack.push(child);
+            }
+        }
+    }
+    for body in 0..count {
+        let noted = in_cycle[body] || parent_lines[body].is_empty();
+        if !reached[body] && !noted {
+            problems.push(Problem::Unreachable {
+                body: names[body].to_string(),
+                root: root.to_string(),
+                lines: parent_lines[body].clone(),
+            });
+        }
+    }
+
+    problems
+}
@@ -0,0 +1,8 @@
+use day6::orbit_map::OrbitMap;
+
+/// The example from the puzzle, with `YOU` and `SAN` added as in part 2.
+pub const EXAMPLE: &str = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN";
+
+pub fn example() -> OrbitMap {
+    day6::parse(EXAMPLE).unwrap()
+}
@@ -0,0 +1,85 @@
+mod example;
+
+use example::{example, EXAMPLE};
+
+#[test]
+fn counts_orbits() {
+    let orbits = day6::parse(&EXAMPLE.replace("\nK)YOU\nI)SAN", "")).unwrap();
+    assert_eq!(orbits.direct_orbits(), 11);
+    assert_eq!(orbits.indirect_orbits(), 31);
+    assert_eq!(orbits.total_orbits(), 42);
+}
+
+#[test]
+fn finds_common_ancestors() {
+    let orbits = example();
+    let body = |name| orbits.body(name).unwrap();
+    let name = |body| orbits.name(body).to_string();
+
+    assert_eq!(
+        orbits.common_ancestor(body("YOU"), body("SAN")).map(name),
+        Some("D".to_string())
+    );
+    assert_eq!(
+        orbits.common_ancestor(body("H"), body("L")).map(name),
+        Some("B".to_string())
+    );
+    assert_eq!(
+        orbits.common_ancestor(body("E"), body("L")).map(name),
+        Some("E".to_string())
+    );
+    assert_eq!(
+        orbits.common_ancestor(body("COM"), body("COM")).map(name),
+        Some("COM".to_string())
+    );
+    assert_eq!(orbits.transfers(body("YOU"), body("SAN")), Some(4));
+    assert_eq!(orbits.distance(body("H"), body("L")), Some(8));
+}
+
+#[test]
+fn measures_subtrees() {
+    let orbits = example();
+    let body = |name| orbits.body(name).unwrap();
+
+    assert_eq!(orbits.subtree_size(body("COM")), orbits.len());
+    assert_eq!(orbits.subtree_size(body("E")), 6);
+    let below = orbits
+        .descendants(body("E"))
+        .map(|body| orbits.name(body))
+        .collect::<Vec<&str>>();
+    assert_eq!(below, ["F", "J", "K", "L", "YOU"]);
+    assert_eq!(orbits.depth(body("YOU")), 7);
+    assert_eq!(orbits.ancestors(body("YOU")).count(), 7);
+}
+
+#[test]
+fn keeps_separate_systems_apart() {
+    let orbits = day6::parse("A)B\nB)C\nX)Y").unwrap();
+    let body = |name| orbits.body(name).unwrap();
+
+    assert_eq!(orbits.roots().count(), 2);
+    assert_eq!(orbits.common_ancestor(body("C"), body("Y")), None);
+    assert_eq!(orbits.distance(body("C"), body("Y")), None);
+}
+
+#[test]
+fn answers_part_two_only_with_a_way_to_santa() {
+    assert_eq!(day6::part2(&example()), Ok(4));
+
+    let orbits = day6::parse(&EXAMPLE.replace("\nK)YOU", "")).unwrap();
+    assert_eq!(
+        day6::part2(&orbits),
+        Err("there is no YOU in the map".to_string())
+    );
+    let orbits = day6::parse(&EXAMPLE.replace("\nI)SAN", "")).unwrap();
+    assert_eq!(
+        day6::part2(&orbits),
+        Err("there is no SAN in the map".to_string())
+    );
+
+    let orbits = day6::parse(&format!("{}\nX)Y\nY)SAN", EXAMPLE.replace("\nI)SAN", ""))).unwrap();
+    assert_eq!(
+        day6::part2(&orbits),
+        Err("there's no way from YOU to SAN".to_string())
+    );
+}
@@ -0,0 +1,110 @@
+mod example;
+
+use day6::orbit_map::{Body, OrbitMap};
+use day6::render::{self, Format, Options};
+use example::example;
+
+fn route(orbits: &OrbitMap) -> Vec<String> {
+    let body = |name| orbits.body(name).unwrap();
+    render::route(orbits, body("YOU"), body("SAN"))
+        .unwrap()
+        .into_iter()
+        .map(|body| orbits.name(body).to_string())
+        .collect()
+}
+
+fn you_to_santa(orbits: &OrbitMap) -> Option<Vec<Body>> {
+    render::route(orbits, orbits.body("YOU")?, orbits.body("SAN")?)
+}
+
+#[test]
+fn finds_the_route() {
+    assert_eq!(
+        route(&example()),
+        vec!["YOU", "K", "J", "E", "D", "I", "SAN"]
+    );
+}
+
+#[test]
+fn draws_the_route_only() {
+    let orbits = example();
+    let options = Options {
+        route: you_to_santa(&orbits),
+        route_only: true,
+        collapse: false,
+    };
+    assert_eq!(
+        render::render(&orbits, Format::Tree, &options),
+        "D *\n\
+         ├── E *\n\
+         │   └── J *\n\
+         │       └── K *\n\
+         │           └── YOU *\n\
+         └── I *\n    \
+             └── SAN *\n\
+         * marks the route from YOU to SAN, 4 transfers\n"
+    );
+}
+
+#[test]
+fn collapses_chains() {
+    let orbits = day6::parse("COM)A\nA)B\nB)C\nC)D\nD)E\nD)F\nF)G").unwrap();
+    let options = Options {
+        collapse: true,
+        ..Options::default()
+    };
+    assert_eq!(
+        render::render(&orbits, Format::Tree, &options),
+        "COM … D (5 bodies)\n\
+         ├── E\n\
+         └── F\n    \
+             └── G\n"
+    );
+    assert!(render::render(&orbits, Format::Dot, &options)
+        .contains("n0 [label=\"COM … D (5 bodies)\", shape=box];"));
+}
+
+#[test]
+fn marks_the_route_in_json() {
+    let orbits = example();
+    let options = Options {
+        route: you_to_santa(&orbits),
+        route_only: true,
+        collapse: true,
+    };
+    let json: serde_json::Value =
+        serde_json::from_str(&render::render(&orbits, Format::Json, &options)).unwrap();
+    assert_eq!(json["route"]["transfers"], 4);
+    assert_eq!(json["roots"][0]["bodies"], serde_json::json!(["D"]));
+    assert_eq!(
+        json["roots"][0]["children"][0]["bodies"],
+        serde_json::json!(["E", "J", "K", "YOU"])
+    );
+    assert_eq!(json["roots"][0]["children"][1]["on_route"], true);
+}
+
+#[test]
+fn leaves_out_transfers_from_something_orbiting_nothing() {
+    let orbits = example();
+    let body = |name| orbits.body(name).unwrap();
+    let options = Options {
+        route: render::route(&orbits, body("COM"), body("C")),
+        route_only: true,
+        collapse: false,
+    };
+    assert_eq!(
+        render::render(&orbits, Format::Tree, &options),
+        "COM *\n\
+         └── B *\n    \
+             └── C *\n\
+         * marks the route from COM to C\n"
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&render::render(&orbits, Format::Json, &options)).unwrap();
+    assert_eq!(
+        json["route"]["bodies"],
+        serde_json::json!(["COM", "B", "C"])
+    );
+    assert_eq!(json["route"]["transfers"], serde_json::Value::Null);
+}
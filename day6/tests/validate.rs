@@ -0,0 +1,87 @@
+use day6::validate::{orbits, validate, Problem};
+
+fn problems(input: &str) -> Vec<Problem> {
+    validate(&orbits(input).unwrap(), "COM")
+}
+
+#[test]
+fn accepts_a_single_system() {
+    assert_eq!(problems("COM)B\nB)C\nB)D\n\nD)E"), []);
+}
+
+#[test]
+fn reports_bodies_with_several_parents() {
+    assert_eq!(
+        problems("COM)A\nCOM)B\nA)C\nB)C"),
+        [Problem::SeveralParents {
+            body: "C".to_string(),
+            lines: vec![3, 4]
+        }]
+    );
+}
+
+#[test]
+fn reports_cycles() {
+    let problems = problems("COM)A\nA)B\nB)C\nC)A\nD)D");
+    let cycles = problems
+        .iter()
+        .filter(|problem| matches!(problem, Problem::Cycle { .. }))
+        .collect::<Vec<&Problem>>();
+    assert_eq!(
+        cycles,
+        [
+            &Problem::Cycle {
+                bodies: vec!["A".to_string(), "B".to_string(), "C".to_string()],
+                lines: vec![2, 3, 4]
+            },
+            &Problem::Cycle {
+                bodies: vec!["D".to_string()],
+                lines: vec![5]
+            },
+        ]
+    );
+    assert!(day6::parse("COM)A\nA)B\nB)C\nC)A").is_err());
+}
+
+#[test]
+fn reports_extra_roots_and_unreachable_bodies() {
+    assert_eq!(
+        problems("COM)A\n\nX)Y\nY)Z"),
+        [
+            Problem::ExtraRoot {
+                body: "X".to_string(),
+                lines: vec![3]
+            },
+            Problem::Unreachable {
+                body: "Y".to_string(),
+                root: "COM".to_string(),
+                lines: vec![3]
+            },
+            Problem::Unreachable {
+                body: "Z".to_string(),
+                root: "COM".to_string(),
+                lines: vec![4]
+            },
+        ]
+    );
+    assert_eq!(
+        problems("A)B"),
+        [
+            Problem::ExtraRoot {
+                body: "A".to_string(),
+                lines: vec![1]
+            },
+            Problem::MissingRoot {
+                body: "COM".to_string()
+            },
+        ]
+    );
+}
+
+#[test]
+fn fails_to_parse_at_the_line_completing_a_problem() {
+    let error = day6::parse("COM)A\nCOM)B\nA)C\nB)C").unwrap_err();
+    assert_eq!(error.line, 4);
+    let error = day6::parse("COM)A\nA)B\nB)A\nA)D").unwrap_err();
+    assert_eq!(error.line, 3);
+}
@@ -0,0 +1,73 @@
+use common::{ParseError, Solution};
+use intcode::{Computer, ComputerResult};
+use itertools::Itertools;
+use std::iter;
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    intcode::parse_program(input)
+}
+
+pub fn part1(memory: &[i64]) -> i64 {
+    (0..=4)
+        .permutations(5)
+        .map(|phases| {
+            phases.iter().fold(0, |acc, &phase| {
+                let mut amp = Computer::new(memory);
+                amp.inputs.push_back(phase);
+                amp.inputs.push_back(acc);
+                let outputs = amp.run_until_blocked().expect("a working program");
+                *outputs.first().expect("output")
+            })
+        })
+        .max()
+        .unwrap()
+}
+
+pub fn part2(memory: &[i64]) -> i64 {
+    (5..=9)
+        .permutations(5)
+        .map(|phases| {
+            let mut amps = phases
+                .iter()
+                .map(|&phase| {
+                    let mut amp = Computer::new(memory);
+                    amp.inputs.push_back(phase);
+                    amp
+                })
+                .collect::<Vec<Computer>>();
+            iter::successors(Some(0), |&input| {
+                phases.iter().enumerate().try_fold(input, |acc, (i, _)| {
+                    amps[i].inputs.push_back(acc);
+                    match amps[i].run().expect("a working program") {
+                        ComputerResult::Output(value) => Some(value),
+                        ComputerResult::Halted => None,
+                        ComputerResult::NeedInput => unreachable!("a phase and a signal"),
+                    }
+                })
+            })
+            .last()
+            .unwrap()
+        })
+        .max()
+        .unwrap()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<i64>) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<i64>) -> i64 {
+        part2(input)
+    }
+}
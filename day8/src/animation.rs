@@ -0,0 +1,98 @@
+//! Shows how a Space Image Format picture is built up by compositing one
+//! layer at a time, from the front layer back.
+
+use crate::sif::{Pixel, SifImage};
+use geometry::{DenseGrid, Point};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cell {
+    pub pixel: Pixel,
+    /// Whether this frame's layer is the one that made the pixel opaque.
+    pub revealed: bool,
+}
+
+/// The composite of the layers up to and including `layer`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub layer: usize,
+    pub cells: DenseGrid<Cell>,
+}
+
+const RESET: &str = "\x1b[0m";
+const REVEALED_WHITE: &str = "\x1b[93m";
+const REVEALED_BLACK: &str = "\x1b[34m";
+
+impl Frame {
+    pub fn revealed(&self) -> usize {
+        self.cells.iter().filter(|(_, cell)| cell.revealed).count()
+    }
+
+    /// Draws the frame with ANSI colors, marking the pixels this layer
+    /// revealed in yellow when white and blue when black. Pixels that are
+    /// still transparent are shaded.
+    pub fn to_terminal(&self) -> String {
+        let mut text = String::new();
+        for y in 0..self.cells.height() {
+            if y > 0 {
+                text.push('\n');
+            }
+            for x in 0..self.cells.width() {
+                let cell = self.cells[Point::new(x as i64, y as i64)];
+                match (cell.pixel, cell.revealed) {
+                    (Pixel::White, false) => text.push('█'),
+                    (Pixel::Black, false) => text.push(' '),
+                    (Pixel::Transparent, _) => text.push('░'),
+                    (Pixel::White, true) => {
+                        text.push_str(REVEALED_WHITE);
+                        text.push('█');
+                        text.push_str(RESET);
+                    }
+                    (Pixel::Black, true) => {
+                        text.push_str(REVEALED_BLACK);
+                        text.push('█');
+                        text.push_str(RESET);
+                    }
+                }
+            }
+        }
+        text
+    }
+}
+
+/// One frame per layer, each adding that layer behind the ones before it.
+pub fn frames(image: &SifImage) -> Vec<Frame> {
+    let mut composite = vec![Pixel::Transparent; image.width * image.height];
+    image
+        .layers
+        .iter()
+        .enumerate()
+        .map(|(layer, pixels)| {
+            let mut cells = DenseGrid::filled(
+                image.width,
+                image.height,
+                Cell {
+                    pixel: Pixel::Transparent,
+                    revealed: false,
+                },
+            );
+            for (offset, (shown, &pixel)) in composite.iter_mut().zip(pixels).enumerate() {
+                let revealed = *shown == Pixel::Transparent && pixel != Pixel::Transparent;
+                if revealed {
+                    *shown = pixel;
+                }
+
+                let point =
+                    Point::new((offset % image.width) as i64, (offset / image.width) as i64);
+                cells.set(
+                    point,
+                    Cell {
+                        pixel: *shown,
+                        revealed,
+                    },
+                );
+            }
+
+            Frame { layer, cells }
+        })
+        .collect()
+}
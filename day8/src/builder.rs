@@ -0,0 +1,248 @@
+//! Authors Space Image Format pictures by stacking source bitmaps so that
+//! their composite is a target picture, the compositor run in reverse.
+//!
+//! Working from the front, a source can become the next layer back once
+//! every pixel it would newly cover matches the target. Covering more pixels
+//! never stops a source from fitting, so placing sources until none fits
+//! finds a stack whenever one exists.
+
+use crate::sif::{Pixel, SifImage};
+use common::Grid;
+use geometry::Point;
+use std::error::Error;
+use std::fmt;
+
+/// Above this many sources, finding the fewest layers falls back to
+/// greedily taking the source that covers the most pixels.
+const EXHAUSTIVE_LIMIT: usize = 16;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BuildError {
+    /// A source or the target isn't `width * height` pixels.
+    Size { expected: usize, found: usize },
+    /// No stack of the sources draws these pixels of the target.
+    Uncovered(Vec<Point>),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::Size { expected, found } => {
+                write!(f, "expected {} pixels, found {}", expected, found)
+            }
+            BuildError::Uncovered(points) => {
+                write!(
+                    f,
+                    "no stack of sources draws {} pixel(s) of the target",
+                    points.len()
+                )?;
+                if let Some(point) = points.first() {
+                    write!(f, ", the first at {}", point)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+pub struct SifBuilder {
+    width: usize,
+    height: usize,
+    sources: Vec<Vec<Pixel>>,
+    fewest_layers: bool,
+}
+
+impl SifBuilder {
+    pub fn new(width: usize, height: usize) -> SifBuilder {
+        SifBuilder {
+            width,
+            height,
+            sources: Vec::new(),
+            fewest_layers: false,
+        }
+    }
+
+    fn check_size(&self, found: usize) -> Result<(), BuildError> {
+        let expected = self.width * self.height;
+        if found == expected {
+            Ok(())
+        } else {
+            Err(BuildError::Size { expected, found })
+        }
+    }
+
+    /// Adds a source given as pixels, where transparent pixels are left for
+    /// the layers behind.
+    pub fn add_pixels(&mut self, pixels: Vec<Pixel>) -> Result<(), BuildError> {
+        self.check_size(pixels.len())?;
+        self.sources.push(pixels);
+        Ok(())
+    }
+
+    /// Adds a source drawing white for the lit pixels of `bitmap` and black
+    /// for the rest, wherever `mask` is lit.
+    pub fn add_bitmap(&mut self, bitmap: &Grid, mask: &Grid) -> Result<(), BuildError> {
+        self.check_size(bitmap.width() * bitmap.height())?;
+        self.check_size(mask.width() * mask.height())?;
+        let pixels = bitmap
+            .iter()
+            .zip(mask.iter())
+            .map(|((_, &lit), (_, &opaque))| match (opaque, lit) {
+                (false, _) => Pixel::Transparent,
+                (true, true) => Pixel::White,
+                (true, false) => Pixel::Black,
+            })
+            .collect();
+        self.add_pixels(pixels)
+    }
+
+    /// Searches for the stack with the fewest layers rather than placing
+    /// sources in the order they were added.
+    pub fn fewest_layers(&mut self, enabled: bool) {
+        self.fewest_layers = enabled;
+    }
+
+    /// Stacks the sources into an image whose composite is `target`. Each
+    /// pixel is opaque in at most one layer, the one that shows it, and
+    /// sources that would show nothing are left out.
+    pub fn build(&self, target: &[Pixel]) -> Result<SifImage, BuildError> {
+        self.check_size(target.len())?;
+
+        let candidates = (0..self.sources.len()).collect::<Vec<usize>>();
+        let order = if !self.fewest_layers {
+            self.peel(target, &candidates, false)
+        } else if self.sources.len() <= EXHAUSTIVE_LIMIT {
+            self.fewest(target)
+        } else {
+            self.peel(target, &candidates, true)
+        };
+
+        let mut covered = vec![false; target.len()];
+        let mut layers = order
+            .iter()
+            .map(|&source| self.contribution(source, &mut covered))
+            .collect::<Vec<Vec<Pixel>>>();
+
+        let uncovered = target
+            .iter()
+            .zip(&covered)
+            .enumerate()
+            .filter(|&(_, (&pixel, &covered))| pixel != Pixel::Transparent && !covered)
+            .map(|(offset, _)| self.point(offset))
+            .collect::<Vec<Point>>();
+        if !uncovered.is_empty() {
+            return Err(BuildError::Uncovered(uncovered));
+        }
+
+        if layers.is_empty() {
+            layers.push(vec![Pixel::Transparent; target.len()]);
+        }
+        Ok(SifImage {
+            width: self.width,
+            height: self.height,
+            layers,
+        })
+    }
+
+    fn point(&self, offset: usize) -> Point {
+        Point::new((offset % self.width) as i64, (offset / self.width) as i64)
+    }
+
+    /// How many uncovered pixels `source` would draw, or `None` if any of
+    /// them would differ from the target.
+    fn fit(&self, source: usize, target: &[Pixel], covered: &[bool]) -> Option<usize> {
+        let mut count = 0;
+        for (offset, &pixel) in self.sources[source].iter().enumerate() {
+            if pixel == Pixel::Transparent || covered[offset] {
+                continue;
+            }
+            if pixel != target[offset] {
+                return None;
+            }
+            count += 1;
+        }
+        Some(count)
+    }
+
+    /// Places candidates front to back until none of them draws anything
+    /// new, returning the order they were placed in. Greedy placement takes
+    /// whichever source draws the most each time, otherwise the earliest
+    /// added source that fits is taken.
+    fn peel(&self, target: &[Pixel], candidates: &[usize], greedy: bool) -> Vec<usize> {
+        let mut covered = vec![false; target.len()];
+        let mut remaining = candidates.to_vec();
+        let mut order = Vec::new();
+        loop {
+            let fits = remaining.iter().enumerate().filter_map(|(i, &source)| {
+                self.fit(source, target, &covered)
+                    .filter(|&count| count > 0)
+                    .map(|count| (i, count))
+            });
+            let next = if greedy {
+                fits.max_by_key(|&(i, count)| (count, std::cmp::Reverse(i)))
+            } else {
+                fits.min_by_key(|&(i, _)| i)
+            };
+
+            let (i, _) = match next {
+                Some(next) => next,
+                None => break order,
+            };
+            let source = remaining.remove(i);
+            for (offset, &pixel) in self.sources[source].iter().enumerate() {
+                covered[offset] |= pixel != Pixel::Transparent;
+            }
+            order.push(source);
+        }
+    }
+
+    /// Tries every set of sources from smallest to largest and returns the
+    /// first whose stack draws the whole target.
+    fn fewest(&self, target: &[Pixel]) -> Vec<usize> {
+        let all = (0..self.sources.len()).collect::<Vec<usize>>();
+        let mut subsets = (0u32..1 << self.sources.len()).collect::<Vec<u32>>();
+        subsets.sort_by_key(|subset| subset.count_ones());
+
+        subsets
+            .into_iter()
+            .map(|subset| {
+                all.iter()
+                    .copied()
+                    .filter(|&source| subset & (1 << source) != 0)
+                    .collect::<Vec<usize>>()
+            })
+            .map(|candidates| self.peel(target, &candidates, false))
+            .find(|order| self.draws(target, order))
+            .unwrap_or_else(|| self.peel(target, &all, false))
+    }
+
+    fn draws(&self, target: &[Pixel], order: &[usize]) -> bool {
+        let mut covered = vec![false; target.len()];
+        for &source in order {
+            self.contribution(source, &mut covered);
+        }
+        target
+            .iter()
+            .zip(&covered)
+            .all(|(&pixel, &covered)| pixel == Pixel::Transparent || covered)
+    }
+
+    /// The pixels `source` shows when placed behind the already `covered`
+    /// ones, marking them covered in turn.
+    fn contribution(&self, source: usize, covered: &mut [bool]) -> Vec<Pixel> {
+        self.sources[source]
+            .iter()
+            .zip(covered.iter_mut())
+            .map(|(&pixel, covered)| {
+                if pixel == Pixel::Transparent || *covered {
+                    Pixel::Transparent
+                } else {
+                    *covered = true;
+                    pixel
+                }
+            })
+            .collect()
+    }
+}
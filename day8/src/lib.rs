@@ -0,0 +1,47 @@
+pub mod animation;
+pub mod builder;
+pub mod sif;
+
+use common::{Grid, ParseError, Solution};
+use sif::SifImage;
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
+pub fn parse(input: &str) -> Result<SifImage, ParseError> {
+    SifImage::decode(input, WIDTH, HEIGHT)
+}
+
+pub fn part1(image: &SifImage) -> usize {
+    let stats = image
+        .layer_stats()
+        .into_iter()
+        .min_by_key(|stats| stats.black)
+        .expect("at least one layer");
+
+    stats.white * stats.transparent
+}
+
+pub fn part2(image: &SifImage) -> Grid {
+    image.to_grid()
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = SifImage;
+    type Part1 = usize;
+    type Part2 = Grid;
+
+    fn parse(input: &str) -> Result<SifImage, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &SifImage) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &SifImage) -> Grid {
+        part2(input)
+    }
+}
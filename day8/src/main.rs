@@ -1,57 +1,96 @@
-use std::io::{self, Read};
+use clap::Parser;
+use day8::animation::{self, Cell};
+use day8::sif::Pixel;
+use day8::Day8;
+use export::{Monochrome, Rgb};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use std::time::Duration;
 
-const WIDTH: usize = 25;
-const HEIGHT: usize = 6;
+#[derive(Debug, Parser)]
+struct Options {
+    #[command(flatten)]
+    export: export::Options,
 
-fn part1(layers: &[Vec<char>]) {
-    let (layer, _) = layers
-        .iter()
-        .map(|layer| layer.iter().filter(|&c| *c == '0').count())
-        .enumerate()
-        .min_by_key(|&(_, count)| count)
-        .unwrap();
+    #[command(flatten)]
+    colors: Monochrome,
 
-    let ones = layers[layer].iter().filter(|&c| *c == '1').count();
-    let twos = layers[layer].iter().filter(|&c| *c == '2').count();
+    /// Play the layers being composited one at a time in the terminal
+    #[arg(long)]
+    animate: bool,
 
-    let answer = ones * twos;
-    println!("part 1 = {}", answer);
-}
+    /// Write the layers being composited to an animated GIF
+    #[arg(long, value_name = "PATH")]
+    gif: Option<PathBuf>,
 
-fn part2(layers: &[Vec<char>]) {
-    println!("part 2:");
-
-    for y in 0..HEIGHT {
-        for x in 0..WIDTH {
-            let offset = y * WIDTH + x;
-            let pixel = layers
-                .iter()
-                .map(|layer| layer[offset])
-                .find(|&pixel| pixel != '2');
-            match pixel {
-                Some('0') | None => print!(" "),
-                Some('1') => print!("█"),
-                _ => unreachable!(),
-            }
-        }
+    /// How long to show each layer for, in milliseconds
+    #[arg(long, default_value_t = 100)]
+    delay: u64,
+}
 
-        println!();
+/// Pixels revealed by a frame's layer are tinted so they stand out.
+fn color(colors: &Monochrome, cell: &Cell) -> Rgb {
+    match (cell.pixel, cell.revealed) {
+        (Pixel::White, false) => colors.lit,
+        (Pixel::Black, false) => colors.unlit,
+        (Pixel::Transparent, _) => Rgb::new(0x40, 0x40, 0x40),
+        (Pixel::White, true) => Rgb::new(0xff, 0xd7, 0x00),
+        (Pixel::Black, true) => Rgb::new(0x00, 0x30, 0x90),
     }
 }
 
 fn main() {
-    let mut input = String::new();
-    io::stdin()
-        .lock()
-        .read_to_string(&mut input)
-        .expect("input");
-
-    let chars = input.trim().chars().collect::<Vec<char>>();
-    let layers = chars
-        .chunks(WIDTH * HEIGHT)
-        .map(|layer| layer.to_vec())
-        .collect::<Vec<Vec<char>>>();
-
-    part1(&layers);
-    part2(&layers);
+    let options = Options::parse();
+    let input = common::read_input::<Day8>();
+    common::print_answers::<Day8>(&input);
+
+    if options.export.is_enabled() {
+        let image = day8::part2(&input);
+        options
+            .export
+            .export(&image, options.colors.palette())
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to write image: {}", e);
+                process::exit(1);
+            });
+    }
+
+    if !options.animate && options.gif.is_none() {
+        return;
+    }
+
+    let delay = Duration::from_millis(options.delay);
+    let frames = animation::frames(&input);
+    if options.animate {
+        for frame in &frames {
+            // Clear the screen and move the cursor back to the top.
+            print!("\x1b[2J\x1b[H");
+            println!(
+                "layer {}/{}: {} pixel(s) revealed",
+                frame.layer + 1,
+                frames.len(),
+                frame.revealed()
+            );
+            println!("{}", frame.to_terminal());
+            thread::sleep(delay);
+        }
+    }
+
+    if let Some(path) = &options.gif {
+        let grids = frames
+            .into_iter()
+            .map(|frame| frame.cells)
+            .collect::<Vec<_>>();
+        let file = BufWriter::new(File::create(path).expect("Failed to create GIF"));
+        let palette = |cell: &Cell| color(&options.colors, cell);
+        export::write_gif(file, &grids, palette, &options.export.style(), delay).unwrap_or_else(
+            |e| {
+                eprintln!("Failed to write GIF: {}", e);
+                process::exit(1);
+            },
+        );
+    }
 }
@@ -0,0 +1,163 @@
+//! The Space Image Format: a picture stored as a stack of layers, each a
+//! run of `width * height` digits, where `0` is black, `1` is white and `2`
+//! is transparent. The first layer is in front.
+
+use common::{Grid, ParseError};
+use geometry::Point;
+use std::fmt::Write;
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Pixel {
+    Black,
+    White,
+    Transparent,
+}
+
+impl Pixel {
+    fn from_digit(digit: char) -> Option<Pixel> {
+        match digit {
+            '0' => Some(Pixel::Black),
+            '1' => Some(Pixel::White),
+            '2' => Some(Pixel::Transparent),
+            _ => None,
+        }
+    }
+
+    fn to_digit(self) -> char {
+        match self {
+            Pixel::Black => '0',
+            Pixel::White => '1',
+            Pixel::Transparent => '2',
+        }
+    }
+}
+
+/// How many pixels of each kind a layer has.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct LayerStats {
+    pub black: usize,
+    pub white: usize,
+    pub transparent: usize,
+}
+
+impl LayerStats {
+    fn of(layer: &[Pixel]) -> LayerStats {
+        let mut stats = LayerStats::default();
+        for pixel in layer {
+            match pixel {
+                Pixel::Black => stats.black += 1,
+                Pixel::White => stats.white += 1,
+                Pixel::Transparent => stats.transparent += 1,
+            }
+        }
+        stats
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SifImage {
+    pub width: usize,
+    pub height: usize,
+    pub layers: Vec<Vec<Pixel>>,
+}
+
+impl SifImage {
+    /// A single layer image of `pixels`, given row by row.
+    pub fn from_raster(width: usize, height: usize, pixels: Vec<Pixel>) -> SifImage {
+        assert_eq!(pixels.len(), width * height, "raster is the wrong size");
+        SifImage {
+            width,
+            height,
+            layers: vec![pixels],
+        }
+    }
+
+    /// A single layer image with white for the lit pixels of `grid`.
+    pub fn from_grid(grid: &Grid) -> SifImage {
+        let pixels = grid
+            .iter()
+            .map(|(_, &lit)| if lit { Pixel::White } else { Pixel::Black })
+            .collect();
+        SifImage::from_raster(grid.width(), grid.height(), pixels)
+    }
+
+    /// Decodes `input`, ignoring surrounding whitespace.
+    pub fn decode(input: &str, width: usize, height: usize) -> Result<SifImage, ParseError> {
+        let data = input.trim();
+        let layer_size = width * height;
+        assert!(layer_size > 0, "layers must have at least one pixel");
+        if data.is_empty() {
+            return Err(ParseError::new(input, data, "expected at least one layer"));
+        }
+
+        let mut pixels = Vec::with_capacity(data.len());
+        for (offset, digit) in data.char_indices() {
+            let pixel = Pixel::from_digit(digit).ok_or_else(|| {
+                let text = &data[offset..offset + digit.len_utf8()];
+                ParseError::new(input, text, "expected a pixel `0`, `1` or `2`")
+            })?;
+            pixels.push(pixel);
+        }
+
+        let partial = pixels.len() % layer_size;
+        if partial != 0 {
+            let text = &data[data.len() - partial..];
+            let message = format!(
+                "expected layers of {} pixels, found a partial layer of {}",
+                layer_size, partial
+            );
+            return Err(ParseError::new(input, text, message));
+        }
+
+        let layers = pixels
+            .chunks(layer_size)
+            .map(|layer| layer.to_vec())
+            .collect();
+        Ok(SifImage {
+            width,
+            height,
+            layers,
+        })
+    }
+
+    /// The digits of every layer, front layer first.
+    pub fn encode(&self) -> String {
+        let mut data = String::with_capacity(self.layers.len() * self.width * self.height);
+        for pixel in self.layers.iter().flatten() {
+            data.write_char(pixel.to_digit()).unwrap();
+        }
+        data
+    }
+
+    pub fn layer_stats(&self) -> Vec<LayerStats> {
+        self.layers
+            .iter()
+            .map(|layer| LayerStats::of(layer))
+            .collect()
+    }
+
+    /// Each pixel of the picture is the first one that isn't transparent
+    /// looking from the front layer back. Pixels that are transparent in
+    /// every layer stay transparent.
+    pub fn composite(&self) -> Vec<Pixel> {
+        (0..self.width * self.height)
+            .map(|offset| {
+                self.layers
+                    .iter()
+                    .map(|layer| layer[offset])
+                    .find(|&pixel| pixel != Pixel::Transparent)
+                    .unwrap_or(Pixel::Transparent)
+            })
+            .collect()
+    }
+
+    /// The composite with white pixels lit.
+    pub fn to_grid(&self) -> Grid {
+        let mut grid = Grid::new(self.width, self.height);
+        for (offset, pixel) in self.composite().into_iter().enumerate() {
+            let point = Point::new((offset % self.width) as i64, (offset / self.width) as i64);
+            grid.set(point, pixel == Pixel::White);
+        }
+        grid
+    }
+}
@@ -0,0 +1,47 @@
+use day8::animation::frames;
+use day8::sif::{Pixel, SifImage};
+use geometry::Point;
+
+#[test]
+fn reveals_each_pixel_at_the_front_most_opaque_layer() {
+    let image = SifImage::decode("0222112222120000", 2, 2).expect("valid image");
+    let frames = frames(&image);
+    assert_eq!(frames.len(), 4);
+
+    let revealed = frames
+        .iter()
+        .map(|frame| {
+            frame
+                .cells
+                .iter()
+                .filter(|(_, cell)| cell.revealed)
+                .map(|(point, cell)| (point, cell.pixel))
+                .collect::<Vec<(Point, Pixel)>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        revealed,
+        [
+            vec![(Point::new(0, 0), Pixel::Black)],
+            vec![(Point::new(1, 0), Pixel::White)],
+            vec![(Point::new(0, 1), Pixel::White)],
+            vec![(Point::new(1, 1), Pixel::Black)],
+        ]
+    );
+    assert!(frames.iter().all(|frame| frame.revealed() == 1));
+    assert_eq!(frames[3].layer, 3);
+}
+
+#[test]
+fn keeps_what_earlier_layers_revealed() {
+    let image = SifImage::decode("0222112222120000", 2, 2).expect("valid image");
+    let frames = frames(&image);
+
+    assert_eq!(frames[0].to_terminal(), "\x1b[34m█\x1b[0m░\n░░");
+    assert_eq!(frames[2].to_terminal(), " █\n\x1b[93m█\x1b[0m░");
+    let last = frames.last().unwrap();
+    assert_eq!(
+        last.cells.map(|cell| cell.pixel == Pixel::White),
+        image.to_grid()
+    );
+}
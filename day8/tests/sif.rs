@@ -0,0 +1,77 @@
+use day8::builder::SifBuilder;
+use day8::sif::{Pixel, SifImage};
+use std::fs;
+
+#[test]
+fn input_round_trips() {
+    let input = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"))
+        .expect("Failed to read input");
+    let image = SifImage::decode(&input, 25, 6).expect("valid image");
+
+    assert_eq!(image.encode(), input.trim());
+    assert_eq!(SifImage::decode(&image.encode(), 25, 6), Ok(image));
+}
+
+#[test]
+fn composite_round_trips() {
+    let image = SifImage::decode("0222112222120000", 2, 2).expect("valid image");
+    let composite = image.composite();
+    assert_eq!(
+        composite,
+        [Pixel::Black, Pixel::White, Pixel::White, Pixel::Black]
+    );
+
+    let flattened = SifImage::from_raster(2, 2, composite);
+    assert_eq!(flattened.encode(), "0110");
+    assert_eq!(flattened.to_grid(), image.to_grid());
+}
+
+#[test]
+fn rejects_bad_images() {
+    let error = SifImage::decode("0122013", 2, 2).unwrap_err();
+    assert_eq!((error.line, error.column, error.text.as_str()), (1, 7, "3"));
+
+    let error = SifImage::decode("012201", 2, 2).unwrap_err();
+    assert_eq!(
+        (error.line, error.column, error.text.as_str()),
+        (1, 5, "01")
+    );
+}
+
+#[test]
+fn builds_the_input_from_its_layers() {
+    let input = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"))
+        .expect("Failed to read input");
+    let image = SifImage::decode(&input, 25, 6).expect("valid image");
+    let target = image.composite();
+
+    let mut builder = SifBuilder::new(25, 6);
+    for layer in image.layers.iter().rev() {
+        builder.add_pixels(layer.clone()).expect("layer size");
+    }
+
+    for &fewest_layers in &[false, true] {
+        builder.fewest_layers(fewest_layers);
+        let built = builder.build(&target).expect("a stack of layers");
+        assert_eq!(built.composite(), target);
+        assert!(built.layers.len() <= image.layers.len());
+    }
+}
+
+#[test]
+fn finds_the_fewest_layers() {
+    use Pixel::{Black, Transparent, White};
+
+    let mut builder = SifBuilder::new(2, 1);
+    builder.add_pixels(vec![White, Transparent]).unwrap();
+    builder.add_pixels(vec![Transparent, Black]).unwrap();
+    builder.add_pixels(vec![White, Black]).unwrap();
+    builder.add_pixels(vec![Black, Black]).unwrap();
+
+    let target = [White, Black];
+    assert_eq!(builder.build(&target).unwrap().encode(), "1220");
+
+    builder.fewest_layers(true);
+    assert_eq!(builder.build(&target).unwrap().encode(), "10");
+    assert!(builder.build(&[Black, White]).is_err());
+}
@@ -0,0 +1,40 @@
+use common::{ParseError, Solution};
+use intcode::Computer;
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    intcode::parse_program(input)
+}
+
+pub fn part1(memory: &[i64]) -> i64 {
+    let mut computer = Computer::new(memory);
+    computer.inputs.push_back(1);
+    let outputs = computer.run_until_blocked().expect("a working program");
+    *outputs.first().expect("BOOST keycode")
+}
+
+pub fn part2(memory: &[i64]) -> i64 {
+    let mut computer = Computer::new(memory);
+    computer.inputs.push_back(2);
+    let outputs = computer.run_until_blocked().expect("a working program");
+    *outputs.first().expect("BOOST keycode")
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<i64>) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<i64>) -> i64 {
+        part2(input)
+    }
+}
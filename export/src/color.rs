@@ -0,0 +1,40 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+    pub const WHITE: Rgb = Rgb::new(0xff, 0xff, 0xff);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb { r, g, b }
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// Parses colors written as `#rrggbb`.
+impl FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Rgb, String> {
+        let error = || format!("expected a color like `#ff8800`, found `{}`", s);
+        let hex = s.strip_prefix('#').ok_or_else(error)?;
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).expect("hex digits");
+        Ok(Rgb::new(channel(0), channel(2), channel(4)))
+    }
+}
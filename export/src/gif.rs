@@ -0,0 +1,68 @@
+use crate::raster::raster;
+use crate::{Rgb, Style};
+use geometry::DenseGrid;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// Writes `frames` as a looping animation, showing each for `delay`. GIFs
+/// are limited to 256 colors across all the frames.
+pub fn write_gif<T, W: Write>(
+    writer: W,
+    frames: &[DenseGrid<T>],
+    palette: impl Fn(&T) -> Rgb,
+    style: &Style,
+    delay: Duration,
+) -> io::Result<()> {
+    let first = frames
+        .first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no frames to write"))?;
+    let (width, height) = style.size(first)?;
+    let (width, height) = match (u16::try_from(width), u16::try_from(height)) {
+        (Ok(width), Ok(height)) => (width, height),
+        _ => {
+            let message = format!("{}x{} is too large for a GIF", width, height);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+        }
+    };
+
+    let mut colors = Vec::new();
+    let mut indices = HashMap::new();
+    let mut rasters = Vec::with_capacity(frames.len());
+    for frame in frames {
+        let pixels = raster(frame, &palette, style)?
+            .into_iter()
+            .map(|color| {
+                if let Some(&index) = indices.get(&color) {
+                    return Ok(index);
+                }
+                let index = u8::try_from(colors.len()).map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "more than 256 colors")
+                })?;
+                indices.insert(color, index);
+                colors.push(color);
+                Ok(index)
+            })
+            .collect::<io::Result<Vec<u8>>>()?;
+        rasters.push(pixels);
+    }
+
+    let table = colors
+        .iter()
+        .flat_map(|color| [color.r, color.g, color.b])
+        .collect::<Vec<u8>>();
+    let mut encoder = gif::Encoder::new(writer, width, height, &table).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    let delay = (delay.as_millis() / 10).min(u128::from(u16::MAX)) as u16;
+    for pixels in rasters {
+        let mut frame = gif::Frame::from_indexed_pixels(width, height, pixels, None);
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+//! Writes grids out as PNG, SVG or animated GIF images, one square of
+//! `scale` pixels per cell, with optional lines between the cells.
+
+mod color;
+mod gif;
+mod png;
+mod raster;
+mod svg;
+
+pub use crate::color::Rgb;
+pub use crate::gif::write_gif;
+pub use crate::png::write_png;
+pub use crate::svg::svg;
+
+use clap::{Args, Parser};
+use geometry::DenseGrid;
+use std::convert::TryFrom;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+
+/// The most pixels an image may have, so a large scale can't exhaust
+/// memory.
+pub const MAX_PIXELS: u64 = 1 << 26;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Style {
+    /// Width and height of each cell in pixels.
+    pub scale: u32,
+    /// Color of the lines drawn around every cell, if any.
+    pub grid_lines: Option<Rgb>,
+}
+
+impl Default for Style {
+    fn default() -> Style {
+        Style {
+            scale: 10,
+            grid_lines: None,
+        }
+    }
+}
+
+impl Style {
+    /// The width of the lines around each cell.
+    fn line_width(&self) -> u32 {
+        if self.grid_lines.is_some() {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Size of the whole image in pixels for a grid of `cells` cells, if
+    /// it fits in a `u32`.
+    fn pixels(&self, cells: usize) -> Option<u32> {
+        let line_width = u64::from(self.line_width());
+        let pixels = (cells as u64)
+            .checked_mul(u64::from(self.scale) + line_width)?
+            .checked_add(line_width)?;
+        u32::try_from(pixels).ok()
+    }
+
+    /// Width and height of the image of `grid`, unless it would have more
+    /// than `MAX_PIXELS` pixels.
+    pub(crate) fn size<T>(&self, grid: &DenseGrid<T>) -> io::Result<(u32, u32)> {
+        match (self.pixels(grid.width()), self.pixels(grid.height())) {
+            (Some(width), Some(height)) if u64::from(width) * u64::from(height) <= MAX_PIXELS => {
+                Ok((width, height))
+            }
+            _ => {
+                let message = format!(
+                    "a {}x{} grid at scale {} is too large to draw",
+                    grid.width(),
+                    grid.height(),
+                    self.scale
+                );
+                Err(io::Error::new(io::ErrorKind::InvalidInput, message))
+            }
+        }
+    }
+}
+
+/// The color of grid lines unless another is asked for.
+const GRID_COLOR: Rgb = Rgb::new(0x40, 0x40, 0x40);
+
+/// A palette for pictures of lit and unlit pixels.
+pub fn monochrome(lit: Rgb, unlit: Rgb) -> impl Fn(&bool) -> Rgb {
+    move |&pixel| if pixel { lit } else { unlit }
+}
+
+// Command line flags for the colors of pictures of lit and unlit pixels.
+#[derive(Debug, Args)]
+#[group(id = "monochrome")]
+pub struct Monochrome {
+    /// Color of lit pixels, as `#rrggbb`
+    #[arg(long, value_name = "COLOR", default_value = "#ffffff")]
+    pub lit: Rgb,
+
+    /// Color of unlit pixels, as `#rrggbb`
+    #[arg(long, value_name = "COLOR", default_value = "#000000")]
+    pub unlit: Rgb,
+}
+
+impl Monochrome {
+    pub fn palette(&self) -> impl Fn(&bool) -> Rgb {
+        monochrome(self.lit, self.unlit)
+    }
+}
+
+// Command line flags for the days whose answers are pictures. Not a doc
+// comment, as clap would show it as the description of each binary.
+#[derive(Debug, Parser)]
+#[group(id = "export")]
+pub struct Options {
+    /// Write the picture to a PNG file
+    #[arg(long, value_name = "PATH")]
+    pub png: Option<PathBuf>,
+
+    /// Write the picture to an SVG file
+    #[arg(long, value_name = "PATH")]
+    pub svg: Option<PathBuf>,
+
+    /// Size of each cell in pixels, up to 100
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..=100))]
+    pub scale: u32,
+
+    /// Draw lines between the cells
+    #[arg(long)]
+    pub grid_lines: bool,
+
+    /// Color of the lines between the cells, as `#rrggbb`; implies
+    /// `--grid-lines`
+    #[arg(long, value_name = "COLOR")]
+    pub grid_color: Option<Rgb>,
+}
+
+impl Options {
+    pub fn style(&self) -> Style {
+        Style {
+            scale: self.scale,
+            grid_lines: match (self.grid_lines, self.grid_color) {
+                (_, Some(color)) => Some(color),
+                (true, None) => Some(GRID_COLOR),
+                (false, None) => None,
+            },
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.png.is_some() || self.svg.is_some()
+    }
+
+    /// Writes `grid` to whichever files were asked for.
+    pub fn export<T>(&self, grid: &DenseGrid<T>, palette: impl Fn(&T) -> Rgb) -> io::Result<()> {
+        let style = self.style();
+        if let Some(path) = &self.png {
+            let file = BufWriter::new(File::create(path)?);
+            write_png(file, grid, &palette, &style)?;
+        }
+
+        if let Some(path) = &self.svg {
+            fs::write(path, svg(grid, &palette, &style)?)?;
+        }
+
+        Ok(())
+    }
+}
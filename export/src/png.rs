@@ -0,0 +1,24 @@
+use crate::raster::raster;
+use crate::{Rgb, Style};
+use geometry::DenseGrid;
+use std::io::{self, Write};
+
+pub fn write_png<T, W: Write>(
+    writer: W,
+    grid: &DenseGrid<T>,
+    palette: impl Fn(&T) -> Rgb,
+    style: &Style,
+) -> io::Result<()> {
+    let (width, height) = style.size(grid)?;
+    let mut encoder = png::Encoder::new(writer, width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let data = raster(grid, palette, style)?
+        .into_iter()
+        .flat_map(|color| [color.r, color.g, color.b])
+        .collect::<Vec<u8>>();
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(&data).map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
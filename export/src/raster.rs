@@ -0,0 +1,40 @@
+use crate::{Rgb, Style};
+use geometry::{DenseGrid, Point};
+use std::io;
+
+/// Paints every pixel of the image, row by row.
+pub(crate) fn raster<T>(
+    grid: &DenseGrid<T>,
+    palette: impl Fn(&T) -> Rgb,
+    style: &Style,
+) -> io::Result<Vec<Rgb>> {
+    let (width, height) = style.size(grid)?;
+    let pitch = style.scale + style.line_width();
+
+    // Which cell a pixel along one axis belongs to, or `None` for a line.
+    let cell = |pixel: u32| {
+        let offset = pixel.checked_sub(style.line_width())?;
+        if offset % pitch < style.scale {
+            Some((offset / pitch) as i64)
+        } else {
+            None
+        }
+    };
+
+    let mut data = Vec::with_capacity(width as usize * height as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let color = match (cell(x), cell(y)) {
+                (Some(x), Some(y)) => grid
+                    .get(Point::new(x, y))
+                    .map(&palette)
+                    .or(style.grid_lines)
+                    .unwrap_or(Rgb::BLACK),
+                _ => style.grid_lines.unwrap_or(Rgb::BLACK),
+            };
+            data.push(color);
+        }
+    }
+
+    Ok(data)
+}
@@ -0,0 +1,69 @@
+use crate::{Rgb, Style};
+use geometry::{DenseGrid, Point};
+use std::fmt::Write;
+use std::io;
+
+/// Draws the grid as one rectangle per run of same colored cells in a row.
+pub fn svg<T>(
+    grid: &DenseGrid<T>,
+    palette: impl Fn(&T) -> Rgb,
+    style: &Style,
+) -> io::Result<String> {
+    let (width, height) = style.size(grid)?;
+    let pitch = style.scale + style.line_width();
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}" shape-rendering="crispEdges">"#,
+        width, height
+    )
+    .unwrap();
+    let background = style.grid_lines.unwrap_or(Rgb::BLACK);
+    writeln!(
+        svg,
+        r#"  <rect width="{}" height="{}" fill="{}"/>"#,
+        width, height, background
+    )
+    .unwrap();
+
+    for y in 0..grid.height() {
+        let colors = (0..grid.width())
+            .map(|x| palette(grid.get(Point::new(x as i64, y as i64)).expect("cell")))
+            .collect::<Vec<Rgb>>();
+
+        let mut start = 0;
+        while start < colors.len() {
+            let run = colors[start..]
+                .iter()
+                .take_while(|&&color| color == colors[start])
+                .count();
+            let cells = run as u32;
+            writeln!(
+                svg,
+                r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                style.line_width() + start as u32 * pitch,
+                style.line_width() + y as u32 * pitch,
+                cells * pitch - style.line_width(),
+                style.scale,
+                colors[start]
+            )
+            .unwrap();
+            start += run;
+        }
+    }
+
+    if let Some(color) = style.grid_lines {
+        write!(svg, r#"  <path stroke="{}" stroke-width="1" d=""#, color).unwrap();
+        for x in 0..=grid.width() as u32 {
+            write!(svg, "M{}.5 0V{}", x * pitch, height).unwrap();
+        }
+        for y in 0..=grid.height() as u32 {
+            write!(svg, "M0 {}.5H{}", y * pitch, width).unwrap();
+        }
+        writeln!(svg, r#""/>"#).unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
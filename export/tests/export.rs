@@ -0,0 +1,101 @@
+use export::{monochrome, svg, write_gif, write_png, Rgb, Style};
+use geometry::{DenseGrid, Point};
+use std::io::ErrorKind;
+use std::time::Duration;
+
+const RED: Rgb = Rgb::new(0xff, 0, 0);
+
+/// Three cells across and two down, with the top left one lit.
+fn picture() -> DenseGrid<bool> {
+    let mut grid = DenseGrid::new(3, 2);
+    grid.set(Point::new(0, 0), true);
+    grid
+}
+
+#[test]
+fn writes_pngs_scaled_up() {
+    let style = Style {
+        scale: 4,
+        grid_lines: Some(RED),
+    };
+    let mut file = Vec::new();
+    write_png(
+        &mut file,
+        &picture(),
+        monochrome(Rgb::WHITE, Rgb::BLACK),
+        &style,
+    )
+    .unwrap();
+
+    let mut reader = png::Decoder::new(&file[..]).read_info().unwrap();
+    let mut data = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut data).unwrap();
+    // Three cells of four pixels and four lines across, two and three down.
+    assert_eq!((info.width, info.height), (16, 11));
+
+    let pixel = |x: usize, y: usize| &data[(y * 16 + x) * 3..][..3];
+    assert_eq!(pixel(0, 0), [0xff, 0, 0]);
+    assert_eq!(pixel(1, 1), [0xff, 0xff, 0xff]);
+    assert_eq!(pixel(6, 1), [0, 0, 0]);
+}
+
+#[test]
+fn draws_svgs_as_runs_of_cells() {
+    let style = Style {
+        scale: 10,
+        grid_lines: None,
+    };
+    let drawn = svg(&picture(), monochrome(Rgb::WHITE, Rgb::BLACK), &style).unwrap();
+    assert_eq!(
+        drawn,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="20" viewBox="0 0 30 20" shape-rendering="crispEdges">
+  <rect width="30" height="20" fill="#000000"/>
+  <rect x="0" y="0" width="10" height="10" fill="#ffffff"/>
+  <rect x="10" y="0" width="20" height="10" fill="#000000"/>
+  <rect x="0" y="10" width="30" height="10" fill="#000000"/>
+</svg>
+"##
+    );
+}
+
+#[test]
+fn writes_a_gif_frame_per_grid() {
+    let frames = vec![picture(), DenseGrid::new(3, 2), picture()];
+    let mut file = Vec::new();
+    let palette = monochrome(Rgb::WHITE, Rgb::BLACK);
+    let delay = Duration::from_millis(200);
+    write_gif(&mut file, &frames, palette, &Style::default(), delay).unwrap();
+
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::Indexed);
+    let mut decoder = options.read_info(&file[..]).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (30, 20));
+    let mut count = 0;
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        assert_eq!(frame.delay, 20);
+        count += 1;
+    }
+    assert_eq!(count, 3);
+}
+
+#[test]
+fn refuses_images_too_large_to_draw() {
+    let style = Style {
+        scale: 10_000,
+        grid_lines: None,
+    };
+    let palette = monochrome(Rgb::WHITE, Rgb::BLACK);
+    let error = write_png(Vec::new(), &picture(), &palette, &style).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert_eq!(
+        error.to_string(),
+        "a 3x2 grid at scale 10000 is too large to draw"
+    );
+
+    let huge = Style {
+        scale: u32::MAX,
+        grid_lines: Some(RED),
+    };
+    assert!(svg(&picture(), &palette, &huge).is_err());
+    assert!(write_gif(Vec::new(), &[picture()], &palette, &huge, Duration::ZERO).is_err());
+}
@@ -0,0 +1,281 @@
+use crate::Point;
+use std::collections::hash_map::{self, HashMap};
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{Index, IndexMut};
+
+/// The smallest rectangle containing a set of points, inclusive of both
+/// corners.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    pub fn new(point: Point) -> Bounds {
+        Bounds {
+            min: point,
+            max: point,
+        }
+    }
+
+    pub fn containing(points: impl IntoIterator<Item = Point>) -> Option<Bounds> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Bounds::new(first), Bounds::include))
+    }
+
+    pub fn include(self, point: Point) -> Bounds {
+        Bounds {
+            min: Point::new(self.min.x.min(point.x), self.min.y.min(point.y)),
+            max: Point::new(self.max.x.max(point.x), self.max.y.max(point.y)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Every point inside the bounds in reading order.
+    pub fn points(self) -> impl Iterator<Item = Point> {
+        (self.min.y..=self.max.y)
+            .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+/// A grid that only stores the cells which have been set, for when the
+/// extent isn't known up front.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn entry(&mut self, point: Point) -> hash_map::Entry<'_, Point, T> {
+        self.cells.entry(point)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+
+    pub fn bounds(&self) -> Option<Bounds> {
+        Bounds::containing(self.cells.keys().copied())
+    }
+
+    /// Copies the set cells into a dense grid covering their bounding box,
+    /// filling the gaps with `default`. The bounding box's top left corner
+    /// becomes the origin.
+    pub fn to_dense(&self, default: T) -> DenseGrid<T>
+    where
+        T: Clone,
+    {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return DenseGrid::filled(0, 0, default),
+        };
+
+        let mut grid = DenseGrid::filled(bounds.width(), bounds.height(), default);
+        for (point, value) in self.iter() {
+            grid.set(point - bounds.min, value.clone());
+        }
+        grid
+    }
+
+    /// Draws the bounding box of the set cells, one line per row.
+    pub fn render(&self, draw: impl Fn(Option<&T>) -> char) -> String {
+        self.bounds()
+            .map(|bounds| {
+                (bounds.min.y..=bounds.max.y)
+                    .map(|y| {
+                        (bounds.min.x..=bounds.max.x)
+                            .map(|x| draw(self.get(Point::new(x, y))))
+                            .collect::<String>()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid::new()
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> SparseGrid<T> {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// A fixed size grid with a cell for every point from the origin to
+/// `(width - 1, height - 1)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DenseGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> DenseGrid<T> {
+    pub fn new(width: usize, height: usize) -> DenseGrid<T>
+    where
+        T: Clone + Default,
+    {
+        DenseGrid::filled(width, height, T::default())
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> DenseGrid<T>
+    where
+        T: Clone,
+    {
+        DenseGrid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width
+            && (point.y as usize) < self.height
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(move |index| &mut self.cells[index])
+    }
+
+    pub fn set(&mut self, point: Point, value: T) {
+        *self.get_mut(point).expect("point outside of grid") = value;
+    }
+
+    /// Every point in the grid in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The orthogonal neighbours of `point` which lie inside the grid.
+    pub fn neighbors(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point
+            .neighbors()
+            .filter_map(move |neighbor| self.get(neighbor).map(|value| (neighbor, value)))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> DenseGrid<U> {
+        DenseGrid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    pub fn render_rows(&self, draw: impl Fn(&T) -> char) -> Vec<String> {
+        if self.width == 0 {
+            return Vec::new();
+        }
+
+        self.cells
+            .chunks(self.width)
+            .map(|row| row.iter().map(&draw).collect())
+            .collect()
+    }
+
+    pub fn render(&self, draw: impl Fn(&T) -> char) -> String {
+        self.render_rows(draw).join("\n")
+    }
+}
+
+impl<T> Index<Point> for DenseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).expect("point outside of grid")
+    }
+}
+
+impl<T> IndexMut<Point> for DenseGrid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point).expect("point outside of grid")
+    }
+}
+
+impl DenseGrid<bool> {
+    /// Lit pixels drawn as solid blocks, for pictures such as the letters
+    /// painted by day 8 and day 11.
+    pub fn rows(&self) -> Vec<String> {
+        self.render_rows(|&lit| if lit { '█' } else { ' ' })
+    }
+}
+
+impl fmt::Display for DenseGrid<bool> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.rows().join("\n"))
+    }
+}
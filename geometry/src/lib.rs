@@ -0,0 +1,7 @@
+mod direction;
+mod grid;
+mod point;
+
+pub use direction::Direction;
+pub use grid::{Bounds, DenseGrid, SparseGrid};
+pub use point::Point;
@@ -0,0 +1,99 @@
+use crate::Direction;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position on a 2D grid. As when drawing to the screen, `y` grows
+/// downwards.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    /// Distance from the origin moving only along the axes.
+    pub fn manhattan(self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> i64 {
+        (self - other).manhattan()
+    }
+
+    /// Distance to `other` when diagonal moves count as a single step.
+    pub fn chebyshev_distance(self, other: Point) -> i64 {
+        let delta = self - other;
+        delta.x.abs().max(delta.y.abs())
+    }
+
+    /// Straight-line distance to `other`.
+    pub fn distance(self, other: Point) -> f64 {
+        let delta = self - other;
+        (delta.x as f64).hypot(delta.y as f64)
+    }
+
+    /// The four points sharing an edge with this one.
+    pub fn neighbors(self) -> impl Iterator<Item = Point> {
+        Direction::ALL
+            .iter()
+            .map(move |&direction| self + direction.offset())
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Point {
+        Point { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+    fn mul(self, scale: i64) -> Point {
+        Point::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
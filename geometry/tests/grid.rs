@@ -0,0 +1,63 @@
+use geometry::{Bounds, DenseGrid, Point, SparseGrid};
+
+#[test]
+fn bounds_contain_their_points() {
+    let points = [Point::new(2, -1), Point::new(-1, 3), Point::new(0, 0)];
+    let bounds = Bounds::containing(points.iter().copied()).unwrap();
+    assert_eq!(bounds.min, Point::new(-1, -1));
+    assert_eq!(bounds.max, Point::new(2, 3));
+    assert_eq!((bounds.width(), bounds.height()), (4, 5));
+    assert!(points.iter().all(|&point| bounds.contains(point)));
+    assert!(!bounds.contains(Point::new(3, 0)));
+    assert_eq!(bounds.points().count(), 20);
+    assert_eq!(bounds.points().next(), Some(bounds.min));
+    assert_eq!(Bounds::containing(vec![]), None);
+}
+
+#[test]
+fn densifies_sparse_grids() {
+    let sparse = vec![(Point::new(-2, 5), 'a'), (Point::new(0, 6), 'b')]
+        .into_iter()
+        .collect::<SparseGrid<char>>();
+    assert_eq!(sparse.render(|cell| *cell.unwrap_or(&'.')), "a..\n..b");
+
+    let dense = sparse.to_dense('.');
+    assert_eq!((dense.width(), dense.height()), (3, 2));
+    assert_eq!(dense[Point::new(0, 0)], 'a');
+    assert_eq!(dense[Point::new(2, 1)], 'b');
+    assert_eq!(dense.render(|&cell| cell), "a..\n..b");
+
+    let empty = SparseGrid::<char>::new().to_dense('.');
+    assert_eq!((empty.width(), empty.height()), (0, 0));
+    assert_eq!(empty.render(|&cell| cell), "");
+}
+
+#[test]
+fn finds_neighbors_inside_the_grid() {
+    let mut grid = DenseGrid::new(3, 2);
+    grid.set(Point::new(1, 0), 5);
+    grid[Point::new(0, 1)] = 7;
+
+    let neighbors = |point| grid.neighbors(point).collect::<Vec<(Point, &i32)>>();
+    assert_eq!(
+        neighbors(Point::new(0, 0)),
+        [(Point::new(1, 0), &5), (Point::new(0, 1), &7)]
+    );
+    assert_eq!(neighbors(Point::new(1, 1)).len(), 3);
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(grid.get(Point::new(0, -1)), None);
+}
+
+#[test]
+fn renders_pictures() {
+    let mut grid = DenseGrid::filled(3, 2, false);
+    grid.set(Point::new(0, 0), true);
+    grid.set(Point::new(2, 1), true);
+    assert_eq!(grid.rows(), ["█  ", "  █"]);
+    assert_eq!(grid.to_string(), "█  \n  █");
+    assert_eq!(
+        grid.map(|&lit| u8::from(lit))
+            .render(|&value| (b'0' + value) as char),
+        "100\n001"
+    );
+}
@@ -0,0 +1,69 @@
+use geometry::{Direction, Point};
+
+#[test]
+fn does_arithmetic() {
+    let mut point = Point::new(3, -4);
+    assert_eq!(point + Point::new(1, 2), Point::new(4, -2));
+    assert_eq!(point - Point::new(1, 2), Point::new(2, -6));
+    assert_eq!(point * 3, Point::new(9, -12));
+    assert_eq!(-point, Point::new(-3, 4));
+
+    point += Point::new(-3, 4);
+    assert_eq!(point, Point::ORIGIN);
+    point -= Point::new(2, 1);
+    assert_eq!(point, Point::from((-2, -1)));
+    assert_eq!(point.to_string(), "(-2, -1)");
+}
+
+#[test]
+fn measures_distances() {
+    let (a, b) = (Point::new(3, -4), Point::new(-1, 2));
+    assert_eq!(a.manhattan(), 7);
+    assert_eq!(a.manhattan_distance(b), 10);
+    assert_eq!(a.chebyshev_distance(b), 6);
+    assert_eq!(a.distance(Point::ORIGIN), 5.0);
+}
+
+#[test]
+fn lists_neighbors_clockwise_from_up() {
+    assert_eq!(
+        Point::new(1, 1).neighbors().collect::<Vec<Point>>(),
+        [
+            Point::new(1, 0),
+            Point::new(2, 1),
+            Point::new(1, 2),
+            Point::new(0, 1)
+        ]
+    );
+}
+
+#[test]
+fn turns_both_ways() {
+    for &direction in Direction::ALL.iter() {
+        assert_eq!(direction.turn_left().turn_right(), direction);
+        assert_eq!(direction.reverse().reverse(), direction);
+        assert_eq!(
+            direction.offset() + direction.reverse().offset(),
+            Point::ORIGIN
+        );
+    }
+
+    // Clockwise on the screen, where `y` grows downwards.
+    let mut direction = Direction::Up;
+    let mut clockwise = vec![];
+    for _ in 0..4 {
+        clockwise.push(direction.offset());
+        direction = direction.turn_right();
+    }
+    assert_eq!(
+        clockwise,
+        [
+            Point::new(0, -1),
+            Point::new(1, 0),
+            Point::new(0, 1),
+            Point::new(-1, 0)
+        ]
+    );
+    assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    assert_eq!(Direction::Left.turn_left(), Direction::Down);
+}
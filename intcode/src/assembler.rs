@@ -0,0 +1,204 @@
+//! Assembles Intcode from the syntax the disassembler writes, along with
+//! labels and comments, keeping track of the line each instruction came
+//! from so a debugger can set breakpoints on the source.
+//!
+//! ```text
+//! ; Prints the numbers from the one read down to 1.
+//!         in [count]
+//! loop:   out [count]
+//!         add [count], -1, [count]
+//!         jnz [count], loop
+//!         hlt
+//! count:  data 0
+//! ```
+//!
+//! Operands are written `12` or `label` for immediates, `[12]` or `[label]`
+//! for positions and `[rb+3]` or `[rb-3]` relative to the base. `data`
+//! places numbers or label addresses in memory as they are.
+
+use crate::disassembler::INSTRUCTIONS;
+use crate::source_map::SourceMap;
+use common::parse::number;
+use common::ParseError;
+use std::collections::HashMap;
+
+enum Value<'a> {
+    Number(i64),
+    Label(&'a str),
+}
+
+struct Operand<'a> {
+    mode: i64,
+    value: Value<'a>,
+}
+
+struct Statement<'a> {
+    line: usize,
+    /// `None` for `data`.
+    opcode: Option<i64>,
+    operands: Vec<Operand<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Assembly {
+    pub memory: Vec<i64>,
+    pub source_map: SourceMap,
+}
+
+fn is_label(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn value<'a>(input: &str, text: &'a str) -> Result<Value<'a>, ParseError> {
+    if is_label(text) {
+        Ok(Value::Label(text))
+    } else {
+        number(input, text).map(Value::Number)
+    }
+}
+
+fn operand<'a>(input: &str, text: &'a str) -> Result<Operand<'a>, ParseError> {
+    let inner = match text.strip_prefix('[') {
+        Some(inner) => inner
+            .strip_suffix(']')
+            .ok_or_else(|| ParseError::new(input, text, "expected `]`"))?
+            .trim(),
+        None => {
+            return Ok(Operand {
+                mode: 1,
+                value: value(input, text)?,
+            })
+        }
+    };
+
+    let offset = match inner.strip_prefix("rb").map(str::trim) {
+        Some("") => 0,
+        Some(offset) if offset.starts_with('+') => number(input, offset[1..].trim())?,
+        Some(offset) if offset.starts_with('-') => {
+            let magnitude = offset[1..].trim();
+            0i64.checked_sub_unsigned(number(input, magnitude)?)
+                .ok_or_else(|| ParseError::new(input, magnitude, "offset out of range"))?
+        }
+        _ => {
+            return Ok(Operand {
+                mode: 0,
+                value: value(input, inner)?,
+            })
+        }
+    };
+    Ok(Operand {
+        mode: 2,
+        value: Value::Number(offset),
+    })
+}
+
+fn statement<'a>(
+    input: &str,
+    line: usize,
+    text: &'a str,
+) -> Result<(Statement<'a>, usize), ParseError> {
+    let (name, rest) = match text.find(char::is_whitespace) {
+        Some(index) => (&text[..index], text[index..].trim()),
+        None => (text, ""),
+    };
+    let operands = if rest.is_empty() {
+        vec![]
+    } else {
+        rest.split(',')
+            .map(|operand_text| operand(input, operand_text.trim()))
+            .collect::<Result<Vec<Operand>, ParseError>>()?
+    };
+
+    if name == "data" {
+        if operands.is_empty() {
+            return Err(ParseError::new(input, text, "expected values after `data`"));
+        }
+        if operands.iter().any(|operand| operand.mode != 1) {
+            return Err(ParseError::new(input, rest, "expected plain values"));
+        }
+        let length = operands.len();
+        let statement = Statement {
+            line,
+            opcode: None,
+            operands,
+        };
+        return Ok((statement, length));
+    }
+
+    let &(opcode, _, count, destination) = INSTRUCTIONS
+        .iter()
+        .find(|instruction| instruction.1 == name)
+        .ok_or_else(|| ParseError::new(input, name, "expected an instruction"))?;
+    if operands.len() != count {
+        let message = format!("`{}` takes {} operand(s)", name, count);
+        return Err(ParseError::new(input, text, message));
+    }
+    if let Some(destination) = destination {
+        if operands[destination].mode == 1 {
+            let message = format!("`{}` can't write to an immediate", name);
+            return Err(ParseError::new(input, text, message));
+        }
+    }
+
+    let statement = Statement {
+        line,
+        opcode: Some(opcode),
+        operands,
+    };
+    Ok((statement, count + 1))
+}
+
+pub fn assemble(input: &str) -> Result<Assembly, ParseError> {
+    let mut labels = HashMap::new();
+    let mut statements = vec![];
+    let mut address = 0;
+
+    for (index, line) in input.lines().enumerate() {
+        let mut text = line.split(';').next().unwrap_or("").trim();
+        while let Some(colon) = text.find(':') {
+            let label = text[..colon].trim();
+            if !is_label(label) {
+                return Err(ParseError::new(input, label, "expected a label"));
+            }
+            if labels.insert(label, address).is_some() {
+                return Err(ParseError::new(input, label, "label defined twice"));
+            }
+            text = text[colon + 1..].trim();
+        }
+
+        if !text.is_empty() {
+            let (statement, length) = statement(input, index + 1, text)?;
+            statements.push((address, statement));
+            address += length;
+        }
+    }
+
+    let mut memory = Vec::with_capacity(address);
+    let mut source_map = SourceMap::default();
+    for (address, statement) in statements {
+        source_map.push(statement.line, address);
+        if let Some(opcode) = statement.opcode {
+            let modes = statement
+                .operands
+                .iter()
+                .rev()
+                .fold(0, |modes, operand| modes * 10 + operand.mode);
+            memory.push(opcode + modes * 100);
+        }
+        for operand in statement.operands {
+            memory.push(match operand.value {
+                Value::Number(value) => value,
+                Value::Label(label) => match labels.get(label) {
+                    Some(&address) => address as i64,
+                    None => return Err(ParseError::new(input, label, "undefined label")),
+                },
+            });
+        }
+    }
+
+    Ok(Assembly { memory, source_map })
+}
@@ -0,0 +1,10 @@
+use intcode::dap::Server;
+use std::io::{self, BufReader};
+
+fn main() {
+    let stdout = io::stdout();
+    let mut server = Server::new(stdout.lock());
+    server
+        .serve(BufReader::new(io::stdin()))
+        .expect("Failed to serve");
+}
@@ -0,0 +1,491 @@
+//! A Debug Adapter Protocol server so DAP-capable editors can launch and
+//! step through Intcode programs. Assembly sources (`.asm`) are assembled
+//! at launch and breakpoints are set on their lines; anything else is read
+//! as a comma-separated program and presented as its disassembly listing,
+//! produced once at launch, so self-modifying programs may drift away from
+//! what is shown.
+//!
+//! Requests are read on their own thread, so a running program can be
+//! paused.
+
+use crate::assembler::assemble;
+use crate::disassembler::disassemble;
+use crate::remote::{Session, StopReason, SLICE};
+use crate::source_map::SourceMap;
+use crate::{parse_program, Computer, Fault};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+const THREAD_ID: u64 = 1;
+const FRAME_ID: u64 = 1;
+const SOURCE_REFERENCE: u64 = 1;
+const REGISTERS_REFERENCE: u64 = 1;
+const MEMORY_REFERENCE: u64 = 2;
+
+/// The most memory values a single `variables` request returns.
+const MAX_VARIABLES: usize = 1000;
+
+pub fn read_message<R: BufRead>(reader: &mut R) -> io::Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            let value = value
+                .trim()
+                .parse::<usize>()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            length = Some(value);
+        }
+    }
+
+    let length = length
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length"))?;
+    let mut buffer = vec![0; length];
+    reader.read_exact(&mut buffer)?;
+    let message = serde_json::from_slice(&buffer)?;
+    Ok(Some(message))
+}
+
+pub fn write_message<W: Write>(writer: &mut W, message: &Value) -> io::Result<()> {
+    let body = serde_json::to_string(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()
+}
+
+/// Reads messages from `reader` on a thread of their own, until it runs
+/// out or fails.
+fn read_messages<R: BufRead + Send + 'static>(mut reader: R) -> Receiver<io::Result<Value>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || loop {
+        let message = match read_message(&mut reader) {
+            Ok(Some(message)) => Ok(message),
+            Ok(None) => break,
+            Err(e) => Err(e),
+        };
+        let failed = message.is_err();
+        if sender.send(message).is_err() || failed {
+            break;
+        }
+    });
+    receiver
+}
+
+enum Action {
+    Nothing,
+    Initialized,
+    Start,
+    Continue,
+    Step,
+    Pause,
+    Disconnect,
+}
+
+struct Debuggee {
+    /// The `source` of every stack frame.
+    source: Value,
+    /// The assembly source or disassembly listing that lines refer to.
+    text: String,
+    source_map: SourceMap,
+    session: Session,
+    stop_on_entry: bool,
+}
+
+pub struct Server<W: Write> {
+    writer: W,
+    seq: u64,
+    debuggee: Option<Debuggee>,
+    running: bool,
+}
+
+impl<W: Write> Server<W> {
+    pub fn new(writer: W) -> Server<W> {
+        Server {
+            writer,
+            seq: 0,
+            debuggee: None,
+            running: false,
+        }
+    }
+
+    fn send(&mut self, mut message: Value) -> io::Result<()> {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+        write_message(&mut self.writer, &message)
+    }
+
+    fn event(&mut self, event: &str, body: Value) -> io::Result<()> {
+        self.send(json!({ "type": "event", "event": event, "body": body }))
+    }
+
+    fn stopped(&mut self, reason: &str, description: Option<&str>) -> io::Result<()> {
+        let mut body = json!({
+            "reason": reason,
+            "threadId": THREAD_ID,
+            "allThreadsStopped": true,
+        });
+        if let Some(description) = description {
+            body["description"] = json!(description);
+        }
+
+        self.event("stopped", body)
+    }
+
+    fn debuggee(&mut self) -> Result<&mut Debuggee, String> {
+        self.debuggee
+            .as_mut()
+            .ok_or_else(|| "no program has been launched".to_string())
+    }
+
+    fn launch(&mut self, arguments: &Value) -> Result<(), String> {
+        let path = arguments["program"]
+            .as_str()
+            .ok_or_else(|| "launch requires a program".to_string())?;
+        let input = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        let name = Path::new(path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "program".to_string());
+
+        let (memory, source, text, source_map) = if path.ends_with(".asm") {
+            let assembly = assemble(&input).map_err(|e| format!("{}: {}", path, e))?;
+            let source = json!({ "name": name, "path": path });
+            (assembly.memory, source, input, assembly.source_map)
+        } else {
+            let memory = parse_program(&input).map_err(|e| format!("{}: {}", path, e))?;
+            let listing = disassemble(&memory);
+            let source = json!({
+                "name": format!("{}.asm", name),
+                "sourceReference": SOURCE_REFERENCE,
+            });
+            (memory, source, listing.text, listing.source_map)
+        };
+
+        let mut computer = Computer::new(&memory);
+        if let Some(values) = arguments["input"].as_array() {
+            computer
+                .inputs
+                .extend(values.iter().filter_map(Value::as_i64));
+        }
+
+        self.debuggee = Some(Debuggee {
+            source,
+            text,
+            source_map,
+            session: Session::new(computer),
+            stop_on_entry: arguments["stopOnEntry"].as_bool().unwrap_or(false),
+        });
+        self.running = false;
+
+        Ok(())
+    }
+
+    fn set_breakpoints(&mut self, arguments: &Value) -> Result<Value, String> {
+        let debuggee = self.debuggee()?;
+        let requested = arguments["breakpoints"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+
+        let mut addresses = vec![];
+        let mut breakpoints = vec![];
+        for breakpoint in requested {
+            let line = breakpoint["line"].as_u64().unwrap_or(0) as usize;
+            match debuggee.source_map.address(line) {
+                Some(address) => {
+                    addresses.push(address);
+                    breakpoints.push(json!({ "verified": true, "line": line }));
+                }
+                None => {
+                    breakpoints.push(json!({
+                        "verified": false,
+                        "line": line,
+                        "message": "no instruction on this line",
+                    }));
+                }
+            }
+        }
+
+        debuggee.session.set_breakpoints(addresses);
+        Ok(json!({ "breakpoints": breakpoints }))
+    }
+
+    fn stack_trace(&mut self) -> Result<Value, String> {
+        let debuggee = self.debuggee()?;
+        let ip = debuggee.session.computer.ip;
+        let line = debuggee.source_map.line(ip).unwrap_or(0);
+        Ok(json!({
+            "stackFrames": [{
+                "id": FRAME_ID,
+                "name": format!("ip = {}", ip),
+                "source": debuggee.source,
+                "line": line,
+                "column": 1,
+            }],
+            "totalFrames": 1,
+        }))
+    }
+
+    fn scopes(&mut self) -> Result<Value, String> {
+        let debuggee = self.debuggee()?;
+        let length = debuggee.session.computer.memory.len();
+        Ok(json!({
+            "scopes": [
+                {
+                    "name": "Registers",
+                    "variablesReference": REGISTERS_REFERENCE,
+                    "expensive": false,
+                },
+                {
+                    "name": "Memory",
+                    "variablesReference": MEMORY_REFERENCE,
+                    "indexedVariables": length,
+                    "expensive": true,
+                },
+            ],
+        }))
+    }
+
+    fn variables(&mut self, arguments: &Value) -> Result<Value, String> {
+        let debuggee = self.debuggee()?;
+        let computer = &debuggee.session.computer;
+        let variables = match arguments["variablesReference"].as_u64() {
+            Some(REGISTERS_REFERENCE) => {
+                let inputs = computer
+                    .inputs
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<String>>();
+                vec![
+                    json!({ "name": "ip", "value": computer.ip.to_string(), "variablesReference": 0 }),
+                    json!({ "name": "rb", "value": computer.rb.to_string(), "variablesReference": 0 }),
+                    json!({ "name": "halted", "value": computer.halted.to_string(), "variablesReference": 0 }),
+                    json!({ "name": "inputs", "value": format!("[{}]", inputs.join(", ")), "variablesReference": 0 }),
+                ]
+            }
+            Some(MEMORY_REFERENCE) => {
+                let start = arguments["start"].as_u64().unwrap_or(0) as usize;
+                let count = arguments["count"]
+                    .as_u64()
+                    .map(|count| count as usize)
+                    .unwrap_or_else(|| computer.memory.len().saturating_sub(start));
+                (start..start.saturating_add(count.min(MAX_VARIABLES)))
+                    .map(|address| {
+                        json!({
+                            "name": format!("[{}]", address),
+                            "value": computer.memory[address].to_string(),
+                            "variablesReference": 0,
+                        })
+                    })
+                    .collect()
+            }
+            _ => return Err("unknown variables reference".to_string()),
+        };
+
+        Ok(json!({ "variables": variables }))
+    }
+
+    /// Evaluates `ip`, `rb`, a memory address such as `12` or `[12]`, or
+    /// `input 1, 2` to queue values for the program to read.
+    fn evaluate(&mut self, arguments: &Value) -> Result<Value, String> {
+        let debuggee = self.debuggee()?;
+        let computer = &mut debuggee.session.computer;
+        let expression = arguments["expression"].as_str().unwrap_or("").trim();
+
+        let result = if let Some(values) = expression.strip_prefix("input") {
+            let values = values
+                .split(',')
+                .map(|value| value.trim().parse::<i64>())
+                .collect::<Result<Vec<i64>, _>>()
+                .map_err(|e| format!("invalid input: {}", e))?;
+            computer.inputs.extend(&values);
+            format!("queued {} input(s)", values.len())
+        } else {
+            match expression {
+                "ip" => computer.ip.to_string(),
+                "rb" => computer.rb.to_string(),
+                _ => {
+                    let address = expression
+                        .trim_start_matches('[')
+                        .trim_end_matches(']')
+                        .parse::<usize>()
+                        .map_err(|_| format!("cannot evaluate `{}`", expression))?;
+                    computer.memory[address].to_string()
+                }
+            }
+        };
+
+        Ok(json!({ "result": result, "variablesReference": 0 }))
+    }
+
+    fn dispatch(&mut self, command: &str, arguments: &Value) -> Result<(Value, Action), String> {
+        let response = match command {
+            "initialize" => (
+                json!({
+                    "supportsConfigurationDoneRequest": true,
+                    "supportsEvaluateForHovers": false,
+                }),
+                Action::Nothing,
+            ),
+            "launch" => {
+                self.launch(arguments)?;
+                (Value::Null, Action::Initialized)
+            }
+            "setBreakpoints" => (self.set_breakpoints(arguments)?, Action::Nothing),
+            "configurationDone" => (Value::Null, Action::Start),
+            "threads" => (
+                json!({ "threads": [{ "id": THREAD_ID, "name": "intcode" }] }),
+                Action::Nothing,
+            ),
+            "stackTrace" => (self.stack_trace()?, Action::Nothing),
+            "scopes" => (self.scopes()?, Action::Nothing),
+            "variables" => (self.variables(arguments)?, Action::Nothing),
+            "source" => {
+                let debuggee = self.debuggee()?;
+                (json!({ "content": debuggee.text }), Action::Nothing)
+            }
+            "evaluate" => (self.evaluate(arguments)?, Action::Nothing),
+            "continue" => (json!({ "allThreadsContinued": true }), Action::Continue),
+            "next" | "stepIn" | "stepOut" => (Value::Null, Action::Step),
+            "pause" => (Value::Null, Action::Pause),
+            "disconnect" | "terminate" => (Value::Null, Action::Disconnect),
+            _ => return Err(format!("unsupported command {}", command)),
+        };
+
+        Ok(response)
+    }
+
+    /// Steps once, or runs another slice of instructions, reporting
+    /// any output and where the program stopped.
+    fn execute(&mut self, stepping: bool) -> io::Result<()> {
+        let (reason, outputs) = match self.debuggee.as_mut() {
+            Some(debuggee) => {
+                let reason = if stepping {
+                    debuggee.session.step().map(Some)
+                } else {
+                    debuggee.session.resume_for(SLICE)
+                };
+                (reason, debuggee.session.take_outputs())
+            }
+            None => return Ok(()),
+        };
+
+        for value in outputs {
+            let body = json!({ "category": "stdout", "output": format!("{}\n", value) });
+            self.event("output", body)?;
+        }
+
+        self.running = reason == Ok(None);
+        match reason {
+            Ok(None) => Ok(()),
+            Ok(Some(StopReason::Step)) => self.stopped("step", None),
+            Ok(Some(StopReason::Breakpoint)) => self.stopped("breakpoint", None),
+            Ok(Some(StopReason::NeedInput)) => self.stopped("pause", Some("waiting for input")),
+            Ok(Some(StopReason::Pause)) => self.stopped("pause", None),
+            Ok(Some(StopReason::Halted)) => {
+                self.event("exited", json!({ "exitCode": 0 }))?;
+                self.event("terminated", json!({}))
+            }
+            Err(fault) => self.fault(fault),
+        }
+    }
+
+    fn fault(&mut self, fault: Fault) -> io::Result<()> {
+        self.stopped("exception", Some(&fault.to_string()))
+    }
+
+    /// Answers `message`, returning `false` once the client disconnects.
+    fn respond(&mut self, message: Value) -> io::Result<bool> {
+        if message["type"] != "request" {
+            return Ok(true);
+        }
+
+        let command = message["command"].as_str().unwrap_or("").to_string();
+        let mut response = json!({
+            "type": "response",
+            "request_seq": message["seq"],
+            "command": command,
+        });
+
+        let action = match self.dispatch(&command, &message["arguments"]) {
+            Ok((body, action)) => {
+                response["success"] = json!(true);
+                if !body.is_null() {
+                    response["body"] = body;
+                }
+                action
+            }
+            Err(error) => {
+                response["success"] = json!(false);
+                response["message"] = json!(error);
+                Action::Nothing
+            }
+        };
+        self.send(response)?;
+
+        match action {
+            Action::Nothing => {}
+            Action::Initialized => self.event("initialized", json!({}))?,
+            Action::Start => {
+                let stop_on_entry = self.debuggee.as_ref().map(|d| d.stop_on_entry);
+                if stop_on_entry == Some(true) {
+                    self.stopped("entry", None)?;
+                } else {
+                    self.running = self.debuggee.is_some();
+                }
+            }
+            Action::Continue => self.running = self.debuggee.is_some(),
+            Action::Step => self.execute(true)?,
+            Action::Pause => {
+                if self.running {
+                    self.running = false;
+                    self.stopped("pause", None)?;
+                }
+            }
+            Action::Disconnect => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// Answers requests read from `reader` until the client disconnects,
+    /// running the program in slices in between while it is running.
+    pub fn serve<R: BufRead + Send + 'static>(&mut self, reader: R) -> io::Result<()> {
+        let messages = read_messages(reader);
+        loop {
+            let message = if self.running {
+                match messages.try_recv() {
+                    Ok(message) => message,
+                    Err(TryRecvError::Empty) => {
+                        self.execute(false)?;
+                        continue;
+                    }
+                    Err(TryRecvError::Disconnected) => break,
+                }
+            } else {
+                match messages.recv() {
+                    Ok(message) => message,
+                    Err(_) => break,
+                }
+            };
+
+            if !self.respond(message?)? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
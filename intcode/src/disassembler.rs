@@ -0,0 +1,109 @@
+//! Linear-sweep disassembly of an Intcode program into a textual listing
+//! with one instruction per line, along with the mapping between listing
+//! lines and memory addresses. Listings are valid input for the assembler,
+//! with each line's address in a comment.
+
+use crate::source_map::SourceMap;
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Operand {
+    Position(i64),
+    Immediate(i64),
+    Relative(i64),
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Operand::Position(address) => write!(f, "[{}]", address),
+            Operand::Immediate(value) => write!(f, "{}", value),
+            Operand::Relative(offset) if offset < 0 => write!(f, "[rb-{}]", offset.unsigned_abs()),
+            Operand::Relative(offset) => write!(f, "[rb+{}]", offset),
+        }
+    }
+}
+
+/// (opcode, name, parameter count, index of the parameter that is written
+/// to) for every instruction.
+pub(crate) const INSTRUCTIONS: [(i64, &str, usize, Option<usize>); 10] = [
+    (1, "add", 3, Some(2)),
+    (2, "mul", 3, Some(2)),
+    (3, "in", 1, Some(0)),
+    (4, "out", 1, None),
+    (5, "jnz", 2, None),
+    (6, "jz", 2, None),
+    (7, "lt", 3, Some(2)),
+    (8, "eq", 3, Some(2)),
+    (9, "arb", 1, None),
+    (99, "hlt", 0, None),
+];
+
+fn mnemonic(opcode: i64) -> Option<(&'static str, usize, Option<usize>)> {
+    INSTRUCTIONS
+        .iter()
+        .find(|instruction| instruction.0 == opcode)
+        .map(|&(_, name, count, destination)| (name, count, destination))
+}
+
+fn decode(memory: &[i64], address: usize) -> Option<(String, usize)> {
+    let instruction = memory[address];
+    if instruction < 0 {
+        return None;
+    }
+
+    let (name, count, destination) = mnemonic(instruction % 100)?;
+    if address + count >= memory.len() {
+        return None;
+    }
+
+    let mut modes = instruction / 100;
+    let mut operands = Vec::with_capacity(count);
+    for position in 0..count {
+        let value = memory[address + 1 + position];
+        let operand = match modes % 10 {
+            0 => Operand::Position(value),
+            1 if destination != Some(position) => Operand::Immediate(value),
+            2 => Operand::Relative(value),
+            _ => return None,
+        };
+        operands.push(operand.to_string());
+        modes /= 10;
+    }
+
+    if modes != 0 {
+        return None;
+    }
+
+    let text = if operands.is_empty() {
+        name.to_string()
+    } else {
+        format!("{} {}", name, operands.join(", "))
+    };
+
+    Some((text, count + 1))
+}
+
+#[derive(Debug)]
+pub struct Listing {
+    pub text: String,
+    pub source_map: SourceMap,
+}
+
+pub fn disassemble(memory: &[i64]) -> Listing {
+    let mut text = String::new();
+    let mut source_map = SourceMap::default();
+
+    let mut address = 0;
+    let mut line = 1;
+    while address < memory.len() {
+        let (instruction, length) =
+            decode(memory, address).unwrap_or_else(|| (format!("data {}", memory[address]), 1));
+        text.push_str(&format!("{:<24} ; {}\n", instruction, address));
+        source_map.push(line, address);
+        address += length;
+        line += 1;
+    }
+
+    Listing { text, source_map }
+}
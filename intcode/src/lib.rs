@@ -0,0 +1,303 @@
+use common::parse::comma_separated;
+use common::ParseError;
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+pub mod assembler;
+pub mod dap;
+pub mod disassembler;
+pub mod remote;
+pub mod source_map;
+
+enum ParameterMode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+#[derive(Debug, Default)]
+pub struct Memory {
+    inner: HashMap<usize, i64>,
+}
+
+impl Memory {
+    /// One past the highest address holding a value.
+    pub fn len(&self) -> usize {
+        self.inner.keys().max().map_or(0, |&max| max + 1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn to_vec(&self) -> Vec<i64> {
+        (0..self.len()).map(|address| self[address]).collect()
+    }
+}
+
+impl From<&[i64]> for Memory {
+    fn from(source: &[i64]) -> Memory {
+        let inner = source
+            .iter()
+            .cloned()
+            .enumerate()
+            .collect::<HashMap<usize, i64>>();
+        Memory { inner }
+    }
+}
+
+impl Index<usize> for Memory {
+    type Output = i64;
+    fn index(&self, index: usize) -> &Self::Output {
+        self.inner.get(&index).unwrap_or(&0)
+    }
+}
+
+impl IndexMut<usize> for Memory {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.inner.entry(index).or_insert(0)
+    }
+}
+
+/// Why the instruction at `ip` couldn't be executed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Fault {
+    UnknownOpcode { ip: usize, instruction: i64 },
+    UnknownMode { ip: usize, instruction: i64 },
+    WriteToImmediate { ip: usize, instruction: i64 },
+    BadAddress { ip: usize, address: i64 },
+    Overflow { ip: usize },
+}
+
+impl Fault {
+    pub fn ip(self) -> usize {
+        match self {
+            Fault::UnknownOpcode { ip, .. }
+            | Fault::UnknownMode { ip, .. }
+            | Fault::WriteToImmediate { ip, .. }
+            | Fault::BadAddress { ip, .. }
+            | Fault::Overflow { ip } => ip,
+        }
+    }
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Fault::UnknownOpcode { ip, instruction } => {
+                write!(f, "unknown opcode in {} at {}", instruction, ip)
+            }
+            Fault::UnknownMode { ip, instruction } => {
+                write!(f, "unknown parameter mode in {} at {}", instruction, ip)
+            }
+            Fault::WriteToImmediate { ip, instruction } => {
+                write!(f, "{} at {} writes to an immediate", instruction, ip)
+            }
+            Fault::BadAddress { ip, address } => {
+                write!(f, "address {} used at {} is out of range", address, ip)
+            }
+            Fault::Overflow { ip } => write!(f, "arithmetic at {} overflows", ip),
+        }
+    }
+}
+
+impl Error for Fault {}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ComputerResult {
+    Output(i64),
+    NeedInput,
+    Halted,
+}
+
+#[derive(Debug)]
+pub struct Computer {
+    pub memory: Memory,
+    pub ip: usize,
+    pub rb: usize,
+    pub inputs: VecDeque<i64>,
+    pub halted: bool,
+}
+
+impl Computer {
+    pub fn new(memory: &[i64]) -> Computer {
+        Computer {
+            memory: memory.into(),
+            ip: 0,
+            rb: 0,
+            inputs: VecDeque::new(),
+            halted: false,
+        }
+    }
+
+    fn read_opcode(instruction: i64) -> i64 {
+        instruction % 100
+    }
+
+    fn read_mode(&self, position: usize) -> Result<ParameterMode, Fault> {
+        let instruction = self.memory[self.ip];
+        match instruction / 10i64.pow(position as u32 + 2) % 10 {
+            0 => Ok(ParameterMode::Position),
+            1 => Ok(ParameterMode::Immediate),
+            2 => Ok(ParameterMode::Relative),
+            _ => Err(Fault::UnknownMode {
+                ip: self.ip,
+                instruction,
+            }),
+        }
+    }
+
+    fn address(&self, value: i64) -> Result<usize, Fault> {
+        usize::try_from(value).map_err(|_| Fault::BadAddress {
+            ip: self.ip,
+            address: value,
+        })
+    }
+
+    fn relative(&self, offset: i64) -> Result<usize, Fault> {
+        let address = (self.rb as i64)
+            .checked_add(offset)
+            .ok_or(Fault::Overflow { ip: self.ip })?;
+        self.address(address)
+    }
+
+    fn read_destination(&self, position: usize) -> Result<usize, Fault> {
+        let source = self.memory[self.ip + position + 1];
+        match self.read_mode(position)? {
+            ParameterMode::Position => self.address(source),
+            ParameterMode::Immediate => Err(Fault::WriteToImmediate {
+                ip: self.ip,
+                instruction: self.memory[self.ip],
+            }),
+            ParameterMode::Relative => self.relative(source),
+        }
+    }
+
+    fn read_parameter(&self, position: usize) -> Result<i64, Fault> {
+        let source = self.memory[self.ip + position + 1];
+        match self.read_mode(position)? {
+            ParameterMode::Position => Ok(self.memory[self.address(source)?]),
+            ParameterMode::Immediate => Ok(source),
+            ParameterMode::Relative => Ok(self.memory[self.relative(source)?]),
+        }
+    }
+
+    fn checked(&self, value: Option<i64>) -> Result<i64, Fault> {
+        value.ok_or(Fault::Overflow { ip: self.ip })
+    }
+
+    /// Executes a single instruction, returning `None` when execution can
+    /// simply carry on with the next one. A fault leaves the computer as it
+    /// was before the instruction.
+    pub fn step(&mut self) -> Result<Option<ComputerResult>, Fault> {
+        if self.halted {
+            return Ok(Some(ComputerResult::Halted));
+        }
+
+        let instruction = self.memory[self.ip];
+        let opcode = Computer::read_opcode(instruction);
+        match opcode {
+            1 => {
+                let left = self.read_parameter(0)?;
+                let right = self.read_parameter(1)?;
+                let destination = self.read_destination(2)?;
+                self.memory[destination] = self.checked(left.checked_add(right))?;
+                self.ip += 4;
+            }
+            2 => {
+                let left = self.read_parameter(0)?;
+                let right = self.read_parameter(1)?;
+                let destination = self.read_destination(2)?;
+                self.memory[destination] = self.checked(left.checked_mul(right))?;
+                self.ip += 4;
+            }
+            3 => {
+                let destination = self.read_destination(0)?;
+                if let Some(value) = self.inputs.pop_front() {
+                    self.memory[destination] = value;
+                    self.ip += 2;
+                } else {
+                    return Ok(Some(ComputerResult::NeedInput));
+                }
+            }
+            4 => {
+                let value = self.read_parameter(0)?;
+                self.ip += 2;
+                return Ok(Some(ComputerResult::Output(value)));
+            }
+            5 => {
+                let cond = self.read_parameter(0)?;
+                if cond != 0 {
+                    self.ip = self.address(self.read_parameter(1)?)?;
+                } else {
+                    self.ip += 3;
+                }
+            }
+            6 => {
+                let cond = self.read_parameter(0)?;
+                if cond == 0 {
+                    self.ip = self.address(self.read_parameter(1)?)?;
+                } else {
+                    self.ip += 3;
+                }
+            }
+            7 => {
+                let left = self.read_parameter(0)?;
+                let right = self.read_parameter(1)?;
+                let destination = self.read_destination(2)?;
+                self.memory[destination] = if left < right { 1 } else { 0 };
+                self.ip += 4;
+            }
+            8 => {
+                let left = self.read_parameter(0)?;
+                let right = self.read_parameter(1)?;
+                let destination = self.read_destination(2)?;
+                self.memory[destination] = if left == right { 1 } else { 0 };
+                self.ip += 4;
+            }
+            9 => {
+                let offset = self.read_parameter(0)?;
+                self.rb = self.relative(offset)?;
+                self.ip += 2;
+            }
+            99 => {
+                self.halted = true;
+                return Ok(Some(ComputerResult::Halted));
+            }
+            _ => {
+                return Err(Fault::UnknownOpcode {
+                    ip: self.ip,
+                    instruction,
+                })
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Runs until the computer outputs a value, needs input or halts.
+    pub fn run(&mut self) -> Result<ComputerResult, Fault> {
+        loop {
+            if let Some(result) = self.step()? {
+                return Ok(result);
+            }
+        }
+    }
+
+    /// Runs until the computer needs input or halts, collecting everything
+    /// it outputs along the way.
+    pub fn run_until_blocked(&mut self) -> Result<Vec<i64>, Fault> {
+        let mut outputs = vec![];
+        while let ComputerResult::Output(value) = self.run()? {
+            outputs.push(value);
+        }
+        Ok(outputs)
+    }
+}
+
+pub fn parse_program(input: &str) -> Result<Vec<i64>, ParseError> {
+    comma_separated(input)
+}
@@ -0,0 +1,26 @@
+use intcode::remote::Session;
+use intcode::{parse_program, Computer};
+use std::env;
+use std::fs;
+use std::net::TcpListener;
+use std::process;
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:4019";
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let path = args.next().expect("usage: intcode <program> [address]");
+    let address = args.next().unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+
+    let input = fs::read_to_string(&path).expect("Failed to read program");
+    let memory = parse_program(&input).unwrap_or_else(|e| {
+        eprintln!("{}: {}", path, e);
+        process::exit(1);
+    });
+
+    let listener = TcpListener::bind(&address).expect("Failed to bind");
+    eprintln!("listening on {}", listener.local_addr().expect("address"));
+
+    let mut session = Session::new(Computer::new(&memory));
+    session.listen(&listener);
+}
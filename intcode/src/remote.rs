@@ -0,0 +1,344 @@
+//! A line-oriented JSON protocol for driving a `Computer` from another
+//! process. Each request is a single JSON object on its own line, tagged by
+//! `command`, and every request is answered with exactly one JSON line:
+//!
+//! ```text
+//! {"command":"set_breakpoint","address":12}
+//! {"status":"ok"}
+//! {"command":"continue"}
+//! {"status":"stopped","reason":"breakpoint","ip":12}
+//! {"command":"read_memory","address":0,"length":4}
+//! {"status":"memory","address":0,"values":[1,380,379,385]}
+//! ```
+//!
+//! Requests are read on their own thread, so a `continue` can be cut short
+//! by a `pause`. Anything else sent while the program runs is answered once
+//! it stops.
+
+use crate::{Computer, ComputerResult, Fault};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+/// How many instructions run between checks for a request to pause.
+pub(crate) const SLICE: usize = 10_000;
+
+/// The most values a single `read_memory` or `snapshot` returns, so a
+/// request can't make the server allocate without bound.
+pub const MAX_VALUES: usize = 1 << 16;
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Request {
+    SetBreakpoint { address: usize },
+    ClearBreakpoint { address: usize },
+    Step,
+    Continue,
+    Pause,
+    ReadMemory { address: usize, length: usize },
+    WriteMemory { address: usize, values: Vec<i64> },
+    PushInput { values: Vec<i64> },
+    ReadOutput,
+    Snapshot,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    Step,
+    Breakpoint,
+    NeedInput,
+    Halted,
+    Pause,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Snapshot {
+    pub ip: usize,
+    pub rb: usize,
+    pub halted: bool,
+    pub memory: Vec<i64>,
+    pub inputs: Vec<i64>,
+    pub outputs: Vec<i64>,
+    pub breakpoints: Vec<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Response {
+    Ok,
+    Stopped { reason: StopReason, ip: usize },
+    Memory { address: usize, values: Vec<i64> },
+    Output { values: Vec<i64> },
+    Snapshot(Snapshot),
+    Error { message: String },
+}
+
+/// Reads lines from `reader` on a thread of their own, until it runs out or
+/// fails.
+fn read_lines<R: BufRead + Send + 'static>(reader: R) -> Receiver<io::Result<String>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in reader.lines() {
+            let failed = line.is_err();
+            if sender.send(line).is_err() || failed {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+fn is_pause(line: &io::Result<String>) -> bool {
+    match line {
+        Ok(line) => matches!(serde_json::from_str(line), Ok(Request::Pause)),
+        Err(_) => false,
+    }
+}
+
+fn error<M: fmt::Display>(message: M) -> Response {
+    Response::Error {
+        message: message.to_string(),
+    }
+}
+
+/// A `Computer` together with the debugger state that outlives any single
+/// client connection.
+pub struct Session {
+    pub computer: Computer,
+    breakpoints: BTreeSet<usize>,
+    outputs: VecDeque<i64>,
+}
+
+impl Session {
+    pub fn new(computer: Computer) -> Session {
+        Session {
+            computer,
+            breakpoints: BTreeSet::new(),
+            outputs: VecDeque::new(),
+        }
+    }
+
+    fn execute(&mut self) -> Result<Option<StopReason>, Fault> {
+        let reason = match self.computer.step()? {
+            None => None,
+            Some(ComputerResult::Output(value)) => {
+                self.outputs.push_back(value);
+                None
+            }
+            Some(ComputerResult::NeedInput) => Some(StopReason::NeedInput),
+            Some(ComputerResult::Halted) => Some(StopReason::Halted),
+        };
+        Ok(reason)
+    }
+
+    /// Executes a single instruction.
+    pub fn step(&mut self) -> Result<StopReason, Fault> {
+        Ok(self.execute()?.unwrap_or(StopReason::Step))
+    }
+
+    /// Runs at most `limit` instructions, stopping early at a breakpoint,
+    /// when input is needed or when the computer halts. Outputs produced
+    /// along the way are buffered. Returns `None` if it is still running.
+    pub fn resume_for(&mut self, limit: usize) -> Result<Option<StopReason>, Fault> {
+        for _ in 0..limit {
+            if let Some(reason) = self.execute()? {
+                return Ok(Some(reason));
+            }
+
+            if self.breakpoints.contains(&self.computer.ip) {
+                return Ok(Some(StopReason::Breakpoint));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Runs until a breakpoint is reached, input is needed or the computer
+    /// halts. Outputs produced along the way are buffered.
+    pub fn resume(&mut self) -> Result<StopReason, Fault> {
+        loop {
+            if let Some(reason) = self.resume_for(usize::MAX)? {
+                return Ok(reason);
+            }
+        }
+    }
+
+    /// Runs like `resume`, a slice at a time, until it stops by itself or a
+    /// `pause` arrives on `lines`. Lines read in the meantime are kept in
+    /// `pending` to be answered next. Returns `None` if the client has gone.
+    fn resume_until_paused(
+        &mut self,
+        lines: &Receiver<io::Result<String>>,
+        pending: &mut VecDeque<io::Result<String>>,
+    ) -> Option<Result<StopReason, Fault>> {
+        loop {
+            match self.resume_for(SLICE) {
+                Ok(None) => {}
+                Ok(Some(reason)) => return Some(Ok(reason)),
+                Err(fault) => return Some(Err(fault)),
+            }
+
+            loop {
+                match lines.try_recv() {
+                    Ok(line) => {
+                        let pause = is_pause(&line);
+                        pending.push_back(line);
+                        if pause {
+                            return Some(Ok(StopReason::Pause));
+                        }
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => return None,
+                }
+            }
+        }
+    }
+
+    pub fn set_breakpoints<I: IntoIterator<Item = usize>>(&mut self, addresses: I) {
+        self.breakpoints = addresses.into_iter().collect();
+    }
+
+    pub fn take_outputs(&mut self) -> Vec<i64> {
+        self.outputs.drain(..).collect()
+    }
+
+    pub fn handle(&mut self, request: Request) -> Response {
+        match request {
+            Request::SetBreakpoint { address } => {
+                self.breakpoints.insert(address);
+                Response::Ok
+            }
+            Request::ClearBreakpoint { address } => {
+                if self.breakpoints.remove(&address) {
+                    Response::Ok
+                } else {
+                    error(format_args!("no breakpoint at {}", address))
+                }
+            }
+            Request::Step => match self.step() {
+                Ok(reason) => Response::Stopped {
+                    reason,
+                    ip: self.computer.ip,
+                },
+                Err(fault) => error(fault),
+            },
+            Request::Continue => match self.resume() {
+                Ok(reason) => Response::Stopped {
+                    reason,
+                    ip: self.computer.ip,
+                },
+                Err(fault) => error(fault),
+            },
+            // Only stops a `continue` that is still running, and `serve`
+            // sees to that before this is answered.
+            Request::Pause => Response::Ok,
+            Request::ReadMemory { address, length } => {
+                if length > MAX_VALUES {
+                    return error(format_args!(
+                        "can read at most {} values at once",
+                        MAX_VALUES
+                    ));
+                }
+                let end = match address.checked_add(length) {
+                    Some(end) => end,
+                    None => return error("memory ends before that"),
+                };
+                let values = (address..end)
+                    .map(|address| self.computer.memory[address])
+                    .collect();
+                Response::Memory { address, values }
+            }
+            Request::WriteMemory { address, values } => {
+                if address.checked_add(values.len()).is_none() {
+                    return error("memory ends before that");
+                }
+                for (offset, value) in values.into_iter().enumerate() {
+                    self.computer.memory[address + offset] = value;
+                }
+                Response::Ok
+            }
+            Request::PushInput { values } => {
+                self.computer.inputs.extend(values);
+                Response::Ok
+            }
+            Request::ReadOutput => Response::Output {
+                values: self.take_outputs(),
+            },
+            Request::Snapshot if self.computer.memory.len() > MAX_VALUES => error(format_args!(
+                "memory holds {} values, too many to snapshot; use read_memory",
+                self.computer.memory.len()
+            )),
+            Request::Snapshot => Response::Snapshot(Snapshot {
+                ip: self.computer.ip,
+                rb: self.computer.rb,
+                halted: self.computer.halted,
+                memory: self.computer.memory.to_vec(),
+                inputs: self.computer.inputs.iter().cloned().collect(),
+                outputs: self.outputs.iter().cloned().collect(),
+                breakpoints: self.breakpoints.iter().cloned().collect(),
+            }),
+        }
+    }
+
+    /// Answers requests read from `reader` until the client disconnects.
+    /// A `continue` runs in slices, watching for a `pause` in between.
+    pub fn serve<R, W>(&mut self, reader: R, mut writer: W) -> io::Result<()>
+    where
+        R: BufRead + Send + 'static,
+        W: Write,
+    {
+        let lines = read_lines(reader);
+        let mut pending = VecDeque::new();
+        loop {
+            let line = match pending.pop_front() {
+                Some(line) => line,
+                None => match lines.recv() {
+                    Ok(line) => line,
+                    Err(_) => break,
+                },
+            }?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let response = match serde_json::from_str(&line) {
+                Ok(Request::Continue) => match self.resume_until_paused(&lines, &mut pending) {
+                    Some(Ok(reason)) => Response::Stopped {
+                        reason,
+                        ip: self.computer.ip,
+                    },
+                    Some(Err(fault)) => error(fault),
+                    None => break,
+                },
+                Ok(request) => self.handle(request),
+                Err(e) => error(e),
+            };
+
+            serde_json::to_writer(&mut writer, &response)?;
+            writeln!(writer)?;
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Serves each client that connects to `listener` in turn, all of them
+    /// sharing the same running computer. A client that fails is logged and
+    /// dropped, and the next one served.
+    pub fn listen(&mut self, listener: &TcpListener) {
+        for stream in listener.incoming() {
+            let served = stream.and_then(|stream| {
+                let reader = BufReader::new(stream.try_clone()?);
+                self.serve(reader, BufWriter::new(stream))
+            });
+            if let Err(e) = served {
+                eprintln!("client failed: {}", e);
+            }
+        }
+    }
+}
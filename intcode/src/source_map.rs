@@ -0,0 +1,33 @@
+//! Relates lines of an assembly source or listing to the addresses of the
+//! instructions written on them.
+
+/// Lines are 1-based, as editors count them. Both lines and addresses only
+/// ever grow from one entry to the next.
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    lines: Vec<usize>,
+    addresses: Vec<usize>,
+}
+
+impl SourceMap {
+    pub(crate) fn push(&mut self, line: usize, address: usize) {
+        self.lines.push(line);
+        self.addresses.push(address);
+    }
+
+    /// The address of the instruction on `line`, if there is one.
+    pub fn address(&self, line: usize) -> Option<usize> {
+        let index = self.lines.binary_search(&line).ok()?;
+        Some(self.addresses[index])
+    }
+
+    /// The line containing `address`, which may fall part way through an
+    /// instruction if the program jumped somewhere unexpected.
+    pub fn line(&self, address: usize) -> Option<usize> {
+        match self.addresses.binary_search(&address) {
+            Ok(index) => Some(self.lines[index]),
+            Err(0) => None,
+            Err(index) => Some(self.lines[index - 1]),
+        }
+    }
+}
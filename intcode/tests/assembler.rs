@@ -0,0 +1,67 @@
+use intcode::assembler::assemble;
+use intcode::disassembler::disassemble;
+
+const DOUBLER: &str = "\
+; Doubles its input.
+start:  in [rb+1]
+        mul [rb + 1], 2, [value]
+
+        out [value] ; the answer
+        jz 0, start
+value:  data 0, start, -3
+";
+
+#[test]
+fn assembles_labels_and_modes() {
+    let assembly = assemble(DOUBLER).unwrap();
+    assert_eq!(
+        assembly.memory,
+        [203, 1, 1202, 1, 2, 11, 4, 11, 1106, 0, 0, 0, 0, -3]
+    );
+
+    let source_map = assembly.source_map;
+    assert_eq!(source_map.address(1), None);
+    assert_eq!(source_map.address(5), Some(6));
+    assert_eq!(source_map.line(9), Some(6));
+    assert_eq!(source_map.line(12), Some(7));
+}
+
+#[test]
+fn reassembles_disassembly() {
+    let program = intcode::parse_program(include_str!("../../day9/input.txt")).unwrap();
+    let listing = disassemble(&program);
+    assert_eq!(assemble(&listing.text).unwrap().memory, program);
+
+    let extremes = [1201, i64::MIN, i64::MAX, 0, 99];
+    let listing = disassemble(&extremes);
+    assert_eq!(assemble(&listing.text).unwrap().memory, extremes);
+}
+
+#[test]
+fn reports_where_assembly_is_wrong() {
+    let error = |source: &str| {
+        let error = assemble(source).unwrap_err();
+        (error.line, error.column, error.message)
+    };
+
+    assert_eq!(
+        error("in [x]\nout [y]\nx: data 0"),
+        (2, 6, "undefined label".to_string())
+    );
+    assert_eq!(
+        error("add 1, 2, 3"),
+        (1, 1, "`add` can't write to an immediate".to_string())
+    );
+    assert_eq!(
+        error("a: hlt\n  a: hlt"),
+        (2, 3, "label defined twice".to_string())
+    );
+    assert_eq!(
+        error("out 1, 2"),
+        (1, 1, "`out` takes 1 operand(s)".to_string())
+    );
+    assert_eq!(
+        error("jnz [rb*2], 0"),
+        (1, 6, "expected a number".to_string())
+    );
+}
@@ -0,0 +1,195 @@
+use intcode::dap::{read_message, write_message, Server};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+fn program(name: &str) -> String {
+    format!("{}/tests/programs/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+/// Drives a server over a loopback connection, as an editor would.
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    seq: u64,
+    events: VecDeque<Value>,
+    outputs: Vec<String>,
+}
+
+impl Client {
+    fn start() -> Client {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            stream.set_nodelay(true).unwrap();
+            let reader = BufReader::new(stream.try_clone().unwrap());
+            Server::new(stream).serve(reader).unwrap();
+        });
+
+        let writer = TcpStream::connect(address).unwrap();
+        writer.set_nodelay(true).unwrap();
+        Client {
+            reader: BufReader::new(writer.try_clone().unwrap()),
+            writer,
+            seq: 0,
+            events: VecDeque::new(),
+            outputs: vec![],
+        }
+    }
+
+    fn receive(&mut self) -> Value {
+        let message = read_message(&mut self.reader).unwrap().expect("a message");
+        if message["event"] == "output" {
+            let output = message["body"]["output"].as_str().unwrap();
+            self.outputs.push(output.trim_end().to_string());
+        }
+        message
+    }
+
+    fn request(&mut self, command: &str, arguments: Value) -> Value {
+        self.seq += 1;
+        let request = json!({
+            "seq": self.seq,
+            "type": "request",
+            "command": command,
+            "arguments": arguments,
+        });
+        write_message(&mut self.writer, &request).unwrap();
+
+        loop {
+            let message = self.receive();
+            if message["type"] == "response" && message["request_seq"] == self.seq {
+                return message;
+            } else if message["type"] == "event" && message["event"] != "output" {
+                self.events.push_back(message);
+            }
+        }
+    }
+
+    /// The next event other than output, which must be called `name`.
+    fn event(&mut self, name: &str) -> Value {
+        let event = match self.events.pop_front() {
+            Some(event) => event,
+            None => loop {
+                let message = self.receive();
+                if message["type"] == "event" && message["event"] != "output" {
+                    break message;
+                }
+            },
+        };
+        assert_eq!(event["event"], name, "{}", event);
+        event
+    }
+
+    fn launch(&mut self, arguments: Value) {
+        self.request("initialize", json!({ "adapterID": "intcode" }));
+        let response = self.request("launch", arguments);
+        assert_eq!(response["success"], true, "{}", response);
+        self.event("initialized");
+    }
+
+    fn line(&mut self) -> Value {
+        let trace = self.request("stackTrace", json!({ "threadId": 1 }));
+        trace["body"]["stackFrames"][0]["line"].clone()
+    }
+}
+
+#[test]
+fn stops_at_breakpoints_on_assembly_lines() {
+    let path = program("countdown.asm");
+    let mut client = Client::start();
+    client.launch(json!({ "program": path, "input": [3] }));
+
+    let response = client.request(
+        "setBreakpoints",
+        json!({
+            "source": { "path": path },
+            "breakpoints": [{ "line": 4 }, { "line": 1 }],
+        }),
+    );
+    let breakpoints = &response["body"]["breakpoints"];
+    assert_eq!(breakpoints[0]["verified"], true);
+    assert_eq!(breakpoints[1]["verified"], false);
+
+    client.request("configurationDone", json!({}));
+    assert_eq!(client.event("stopped")["body"]["reason"], "breakpoint");
+    assert_eq!(client.outputs, ["3"]);
+
+    let trace = client.request("stackTrace", json!({ "threadId": 1 }));
+    let frame = &trace["body"]["stackFrames"][0];
+    assert_eq!(frame["line"], 4);
+    assert_eq!(frame["source"]["path"], path.as_str());
+    let count = client.request("evaluate", json!({ "expression": "[12]" }));
+    assert_eq!(count["body"]["result"], "3");
+
+    client.request("continue", json!({ "threadId": 1 }));
+    assert_eq!(client.event("stopped")["body"]["reason"], "breakpoint");
+    assert_eq!(client.outputs, ["3", "2"]);
+
+    client.request("next", json!({ "threadId": 1 }));
+    assert_eq!(client.event("stopped")["body"]["reason"], "step");
+    assert_eq!(client.line(), 5);
+
+    let response = client.request(
+        "setBreakpoints",
+        json!({ "source": { "path": path }, "breakpoints": [] }),
+    );
+    assert_eq!(response["success"], true);
+    client.request("continue", json!({ "threadId": 1 }));
+    client.event("exited");
+    client.event("terminated");
+    assert_eq!(client.outputs, ["3", "2", "1"]);
+}
+
+#[test]
+fn pauses_a_program_that_never_halts() {
+    let mut client = Client::start();
+    client.launch(json!({ "program": program("spin.asm") }));
+
+    client.request("configurationDone", json!({}));
+    let response = client.request("pause", json!({ "threadId": 1 }));
+    assert_eq!(response["success"], true);
+    assert_eq!(client.event("stopped")["body"]["reason"], "pause");
+    assert_eq!(client.line(), 2);
+
+    client.request("continue", json!({ "threadId": 1 }));
+    client.request("pause", json!({ "threadId": 1 }));
+    assert_eq!(client.event("stopped")["body"]["reason"], "pause");
+    client.request("disconnect", json!({}));
+}
+
+#[test]
+fn stops_on_faults_and_bounds_memory_views() {
+    let mut client = Client::start();
+    client.launch(json!({ "program": program("fault.txt"), "stopOnEntry": true }));
+    client.request("configurationDone", json!({}));
+    client.event("stopped");
+
+    let source = client.request("source", json!({ "sourceReference": 1 }));
+    assert!(source["body"]["content"]
+        .as_str()
+        .unwrap()
+        .starts_with("data 77"));
+
+    client.request("next", json!({ "threadId": 1 }));
+    let stopped = client.event("stopped");
+    assert_eq!(stopped["body"]["reason"], "exception");
+    assert_eq!(stopped["body"]["description"], "unknown opcode in 77 at 0");
+
+    let variables = client.request(
+        "variables",
+        json!({ "variablesReference": 2, "start": 0, "count": 1_000_000_000u64 }),
+    );
+    let variables = variables["body"]["variables"].as_array().unwrap();
+    assert!(variables.len() <= 1000);
+    assert_eq!(variables[0]["value"], "77");
+
+    let variables = client.request(
+        "variables",
+        json!({ "variablesReference": 2, "start": u64::MAX - 1 }),
+    );
+    assert_eq!(variables["success"], true);
+}
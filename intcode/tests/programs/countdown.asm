@@ -0,0 +1,7 @@
+; Prints the numbers from the one read down to 1.
+        in [count]
+loop:   out [count]
+        add [count], -1, [count]
+        jnz [count], loop
+        hlt
+count:  data 0
@@ -0,0 +1,2 @@
+; Never halts.
+loop:   jnz 1, loop
@@ -0,0 +1,168 @@
+use intcode::remote::{Session, MAX_VALUES};
+use intcode::Computer;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+
+/// Outputs each input doubled, halting when it reads a zero.
+const DOUBLER: [i64; 16] = [
+    3, 15, 1006, 15, 14, 102, 2, 15, 15, 4, 15, 1105, 1, 0, 99, 0,
+];
+
+/// Serves `program` on a free local port for the rest of the test run.
+fn serve(program: &[i64]) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let mut session = Session::new(Computer::new(program));
+    thread::spawn(move || session.listen(&listener));
+    address
+}
+
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    fn connect(address: SocketAddr) -> Client {
+        let writer = TcpStream::connect(address).unwrap();
+        let reader = BufReader::new(writer.try_clone().unwrap());
+        Client { reader, writer }
+    }
+
+    fn send(&mut self, request: Value) {
+        self.writer
+            .write_all(format!("{}\n", request).as_bytes())
+            .unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    fn request(&mut self, request: Value) -> Value {
+        self.send(request);
+        self.receive()
+    }
+}
+
+#[test]
+fn runs_to_breakpoints_and_reads_memory() {
+    let mut client = Client::connect(serve(&DOUBLER));
+
+    let ok = json!({ "status": "ok" });
+    assert_eq!(
+        client.request(json!({ "command": "set_breakpoint", "address": 9 })),
+        ok
+    );
+    assert_eq!(
+        client.request(json!({ "command": "push_input", "values": [21] })),
+        ok
+    );
+    assert_eq!(
+        client.request(json!({ "command": "continue" })),
+        json!({ "status": "stopped", "reason": "breakpoint", "ip": 9 })
+    );
+    assert_eq!(
+        client.request(json!({ "command": "read_memory", "address": 15, "length": 1 })),
+        json!({ "status": "memory", "address": 15, "values": [42] })
+    );
+    assert_eq!(
+        client.request(json!({ "command": "step" })),
+        json!({ "status": "stopped", "reason": "step", "ip": 11 })
+    );
+    assert_eq!(
+        client.request(json!({ "command": "read_output" })),
+        json!({ "status": "output", "values": [42] })
+    );
+    assert_eq!(
+        client.request(json!({ "command": "continue" })),
+        json!({ "status": "stopped", "reason": "need_input", "ip": 0 })
+    );
+
+    client.request(json!({ "command": "push_input", "values": [0] }));
+    assert_eq!(
+        client.request(json!({ "command": "continue" })),
+        json!({ "status": "stopped", "reason": "halted", "ip": 14 })
+    );
+    let snapshot = client.request(json!({ "command": "snapshot" }));
+    assert_eq!(snapshot["halted"], true);
+    assert_eq!(snapshot["breakpoints"], json!([9]));
+}
+
+#[test]
+fn reports_faults_without_stopping_the_server() {
+    let mut client = Client::connect(serve(&DOUBLER));
+
+    client.request(json!({ "command": "write_memory", "address": 0, "values": [77] }));
+    let response = client.request(json!({ "command": "step" }));
+    assert_eq!(response["status"], "error");
+    assert_eq!(response["message"], "unknown opcode in 77 at 0");
+
+    let response = client.request(json!({ "command": "continue" }));
+    assert_eq!(response["status"], "error");
+    assert_eq!(
+        client.request(json!({ "command": "read_memory", "address": 0, "length": 2 })),
+        json!({ "status": "memory", "address": 0, "values": [77, 15] })
+    );
+}
+
+#[test]
+fn refuses_reads_too_large_to_answer() {
+    let mut client = Client::connect(serve(&DOUBLER));
+
+    let request = json!({ "command": "read_memory", "address": usize::MAX, "length": 2 });
+    assert_eq!(client.request(request)["status"], "error");
+    let request = json!({ "command": "read_memory", "address": 0, "length": MAX_VALUES + 1 });
+    assert_eq!(client.request(request)["status"], "error");
+    let request = json!({ "command": "write_memory", "address": usize::MAX, "values": [1, 2] });
+    assert_eq!(client.request(request)["status"], "error");
+
+    let request = json!({ "command": "write_memory", "address": 1_000_000_000, "values": [1] });
+    assert_eq!(client.request(request)["status"], "ok");
+    assert_eq!(
+        client.request(json!({ "command": "snapshot" }))["status"],
+        "error"
+    );
+}
+
+#[test]
+fn serves_the_next_client_after_one_fails() {
+    let address = serve(&DOUBLER);
+
+    let mut broken = TcpStream::connect(address).unwrap();
+    broken.write_all(b"\xff\xfe\n").unwrap();
+    drop(broken);
+
+    let mut client = Client::connect(address);
+    assert_eq!(
+        client.request(json!({ "command": "read_memory", "address": 0, "length": 1 })),
+        json!({ "status": "memory", "address": 0, "values": [3] })
+    );
+}
+
+#[test]
+fn pauses_a_program_that_never_stops() {
+    // Jumps back to itself forever.
+    let mut client = Client::connect(serve(&[1105, 1, 0]));
+
+    client.send(json!({ "command": "continue" }));
+    client.send(json!({ "command": "read_memory", "address": 0, "length": 1 }));
+    client.send(json!({ "command": "pause" }));
+    assert_eq!(
+        client.receive(),
+        json!({ "status": "stopped", "reason": "pause", "ip": 0 })
+    );
+    assert_eq!(
+        client.receive(),
+        json!({ "status": "memory", "address": 0, "values": [1105] })
+    );
+    assert_eq!(client.receive(), json!({ "status": "ok" }));
+    assert_eq!(
+        client.request(json!({ "command": "step" })),
+        json!({ "status": "stopped", "reason": "step", "ip": 0 })
+    );
+}